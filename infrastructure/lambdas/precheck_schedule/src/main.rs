@@ -44,6 +44,18 @@ async fn function_handler(event: LambdaEvent<Request>) -> Result<Response, Strin
                     pigeons, pigeon_holes
                 )),
                 ValidationError::InvalidData { error: _ } => Some(String::from("Invalid data!")),
+                ValidationError::OutOfBounds { meeting } => Some(format!(
+                    "Meeting {} has no time left between its earliest start and latest end!",
+                    meeting
+                )),
+                ValidationError::FixedTimeUnavailable { meeting } => Some(format!(
+                    "Meeting {} can not take place at its fixed time!",
+                    meeting
+                )),
+                ValidationError::LoadLimitExceeded { participant, limit } => Some(format!(
+                    "Participant {} is limited to {}!",
+                    participant, limit
+                )),
                 _ => None,
             },
         },
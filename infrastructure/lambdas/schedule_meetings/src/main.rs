@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use std::env;
 use zeitplan_libs::{
     schedule::ValidationError,
-    schedule::{MeetingTime, Schedule},
+    schedule::{Diagnosis, MeetingTime, Schedule},
 };
 
 use serde::{Deserialize, Serialize};
@@ -15,8 +15,14 @@ use serde::{Deserialize, Serialize};
 struct Request {
     schedule: Schedule<u16>,
     count: Option<usize>,
+    /// Place as many meetings as possible instead of failing, and report the rest as failed
+    #[serde(default)]
+    partial: bool,
 }
 
+/// The most iterations a partial schedule is searched for, when the request gives no count
+const PARTIAL_COUNT: usize = 100_000;
+
 /// This is a made-up example of what a response structure may look like.
 /// There is no restriction on what it can be. The runtime requires responses
 /// to be serialized into json. The runtime pays no attention
@@ -26,6 +32,9 @@ struct Response {
     results: Vec<MeetingTime<u16>>,
     failed: HashSet<String>,
     attempts: usize,
+    /// Why the failed meetings did not fit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diagnosis: Option<Diagnosis<u16>>,
 }
 
 /// This is the main body for the function.
@@ -54,9 +63,31 @@ async fn function_handler(event: LambdaEvent<Request>) -> Result<Response, Valid
         .ok()
         .flatten();
 
-    schedule
-        .schedule_meetings(event.payload.count, per_thread, num_shuffles)
-        .map(|result| {
+    if event.payload.partial {
+        let count = event.payload.count.unwrap_or(PARTIAL_COUNT);
+        return schedule
+            .schedule_partial(Some(count), per_thread, num_shuffles)
+            .map(|partial| Response {
+                diagnosis: if partial.dropped.is_empty() {
+                    None
+                } else {
+                    schedule.diagnose(event.payload.count).ok().flatten()
+                },
+                results: partial.result.results,
+                failed: partial.dropped.into_iter().collect(),
+                attempts: partial.result.count,
+            });
+    }
+
+    match schedule.schedule_meetings(event.payload.count, per_thread, num_shuffles) {
+        // Not a single meeting is placed, but the diagnosis tells the caller why
+        Err(ValidationError::NoSolution) => Ok(Response {
+            results: vec![],
+            failed: meeting_ids,
+            attempts: 0,
+            diagnosis: schedule.diagnose(event.payload.count)?,
+        }),
+        outcome => outcome.map(|result| {
             let results: Vec<MeetingTime<u16>> = result.results;
 
             let failed: HashSet<String> = meeting_ids
@@ -68,8 +99,10 @@ async fn function_handler(event: LambdaEvent<Request>) -> Result<Response, Valid
                 results,
                 failed,
                 attempts: result.count,
+                diagnosis: None,
             }
-        })
+        }),
+    }
 }
 
 #[tokio::main]
@@ -2,7 +2,7 @@ use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion
 use zeitplan_libs::meeting::{Meeting, MeetingParticipants};
 use zeitplan_libs::participant::Participant;
 use zeitplan_libs::schedule::Schedule;
-use zeitplan_libs::time::{Available, Pigeons, TimeMerge, TimeRange, Windowed};
+use zeitplan_libs::time::{Available, Pigeons, TimeMerge, TimeRange, TimeSet, Windowed};
 
 fn get_participant_avaiability(c: &mut Criterion) {
     let blocked_times = vec![
@@ -11,13 +11,14 @@ fn get_participant_avaiability(c: &mut Criterion) {
         TimeRange::new(20, 22),
         TimeRange::new(24, 31),
     ];
-    let participant: Participant<u8> = Participant::new(&"1", blocked_times);
+    let participant: Participant<u8> = Participant::new("1", blocked_times);
 
-    let available_time = vec![
+    let available_time: TimeSet<u8> = vec![
         TimeRange::new(0, 9),
         TimeRange::new(22, 24),
         TimeRange::new(30, 35),
-    ];
+    ]
+    .into();
 
     c.bench_function("gets_participant_availability", |b| {
         b.iter_batched(
@@ -29,7 +30,7 @@ fn get_participant_avaiability(c: &mut Criterion) {
 }
 
 fn merge_times(c: &mut Criterion) {
-    let input = vec![
+    let input = [
         TimeRange::new(1, 1),
         TimeRange::new(1, 1),
         TimeRange::new(2, 3),
@@ -65,18 +66,19 @@ fn get_meeting_availability(c: &mut Criterion) {
     let blocked_times_2 = vec![TimeRange::new(24, 31), TimeRange::new(20, 21)];
 
     let participants: Vec<Participant<u8>> = vec![
-        Participant::new(&"1", blocked_times_1),
-        Participant::new(&"2", blocked_times_2),
+        Participant::new("1", blocked_times_1),
+        Participant::new("2", blocked_times_2),
     ];
 
-    let meeting = MeetingParticipants::new(&"1", participants, 2);
+    let meeting = MeetingParticipants::new("1", participants, 2);
     let meeting: Meeting<u8> = meeting.into();
 
-    let available_time = vec![
+    let available_time: TimeSet<u8> = vec![
         TimeRange::new(0, 9),
         TimeRange::new(22, 24),
         TimeRange::new(30, 35),
-    ];
+    ]
+    .into();
 
     c.bench_function("gets_meeting_availability", |b| {
         b.iter_batched(
@@ -88,7 +90,7 @@ fn get_meeting_availability(c: &mut Criterion) {
 }
 
 fn windows(c: &mut Criterion) {
-    let available_time = vec![
+    let available_time = [
         TimeRange::new(0, 6),
         TimeRange::new(22, 24),
         TimeRange::new(30, 33),
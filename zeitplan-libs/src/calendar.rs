@@ -0,0 +1,373 @@
+use crate::schedule::MeetingTime;
+use crate::time::{TimeRange, TimeSet};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDateTime, Utc};
+use num::{FromPrimitive, Integer, One, ToPrimitive};
+use std::fmt::{Debug, Display};
+use thiserror::Error;
+
+#[cfg(feature = "tz")]
+mod zone;
+#[cfg(feature = "ics")]
+pub(crate) use zone::{to_utc, Fold};
+#[cfg(feature = "tz")]
+pub use zone::{LocalMeetingTime, LocalParticipant, LocalTimeRange, WorkingHours};
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum CalendarError {
+    #[error("Slot granularity must be at least one millisecond, but was {0}")]
+    InvalidGranularity(Duration),
+    #[error("Horizon end {end} is not after the epoch {epoch}")]
+    EmptyHorizon {
+        epoch: NaiveDateTime,
+        end: NaiveDateTime,
+    },
+    #[error("The horizon holds {0} slots, which do not fit within the slot type")]
+    HorizonOverflow(i64),
+}
+
+/// A point in time which can be placed on a `SlotCalendar`.
+///
+/// Naive date times are used as they are. Date times with an offset are
+/// converted to UTC first, so the calendar's epoch should be given in UTC
+/// whenever zoned times are mixed in.
+pub trait CalendarTime: Sized {
+    fn to_naive(&self) -> NaiveDateTime;
+
+    /// Returns `None` if `naive` can not be represented by this type
+    fn from_naive(naive: NaiveDateTime) -> Option<Self>;
+}
+
+impl CalendarTime for NaiveDateTime {
+    fn to_naive(&self) -> NaiveDateTime {
+        *self
+    }
+
+    fn from_naive(naive: NaiveDateTime) -> Option<Self> {
+        Some(naive)
+    }
+}
+
+impl CalendarTime for DateTime<Utc> {
+    fn to_naive(&self) -> NaiveDateTime {
+        self.naive_utc()
+    }
+
+    fn from_naive(naive: NaiveDateTime) -> Option<Self> {
+        Some(DateTime::from_naive_utc_and_offset(naive, Utc))
+    }
+}
+
+impl CalendarTime for DateTime<FixedOffset> {
+    fn to_naive(&self) -> NaiveDateTime {
+        self.naive_utc()
+    }
+
+    fn from_naive(naive: NaiveDateTime) -> Option<Self> {
+        Some(DateTime::from_naive_utc_and_offset(
+            naive,
+            FixedOffset::east_opt(0)?,
+        ))
+    }
+}
+
+#[cfg(feature = "time")]
+impl CalendarTime for ::time::PrimitiveDateTime {
+    fn to_naive(&self) -> NaiveDateTime {
+        use chrono::{NaiveDate, NaiveTime};
+
+        // Every valid `time` date and time is also valid within chrono
+        NaiveDate::from_ymd_opt(self.year(), self.month() as u32, self.day() as u32)
+            .and_then(|date| {
+                Some(date.and_time(NaiveTime::from_hms_nano_opt(
+                    self.hour() as u32,
+                    self.minute() as u32,
+                    self.second() as u32,
+                    self.nanosecond(),
+                )?))
+            })
+            .expect("time::PrimitiveDateTime is always a valid chrono::NaiveDateTime")
+    }
+
+    fn from_naive(naive: NaiveDateTime) -> Option<Self> {
+        use chrono::{Datelike, Timelike};
+        use std::convert::TryFrom;
+
+        let date = ::time::Date::from_calendar_date(
+            naive.year(),
+            ::time::Month::try_from(naive.month() as u8).ok()?,
+            naive.day() as u8,
+        )
+        .ok()?;
+        let time = ::time::Time::from_hms_nano(
+            naive.hour() as u8,
+            naive.minute() as u8,
+            naive.second() as u8,
+            naive.nanosecond(),
+        )
+        .ok()?;
+
+        Some(::time::PrimitiveDateTime::new(date, time))
+    }
+}
+
+#[cfg(feature = "time")]
+impl CalendarTime for ::time::OffsetDateTime {
+    fn to_naive(&self) -> NaiveDateTime {
+        let utc = self.to_offset(::time::UtcOffset::UTC);
+        ::time::PrimitiveDateTime::new(utc.date(), utc.time()).to_naive()
+    }
+
+    fn from_naive(naive: NaiveDateTime) -> Option<Self> {
+        Some(::time::PrimitiveDateTime::from_naive(naive)?.assume_utc())
+    }
+}
+
+/// Maps slot indices to wall-clock times.
+///
+/// Slot `0` starts at the `epoch`, and every slot lasts `granularity`.
+/// The horizon is the range of slots which fit between the epoch and the
+/// end given to `SlotCalendar::new`.
+///
+/// # Examples
+/// ```
+/// use chrono::{Duration, NaiveDate, NaiveDateTime};
+/// use zeitplan_libs::calendar::SlotCalendar;
+/// use zeitplan_libs::time::TimeRange;
+///
+/// let monday = NaiveDate::from_ymd_opt(2022, 8, 1).unwrap();
+/// let calendar: SlotCalendar<u16> = SlotCalendar::new(
+///     monday.and_hms_opt(0, 0, 0).unwrap(),
+///     Duration::minutes(30),
+///     monday.and_hms_opt(0, 0, 0).unwrap() + Duration::weeks(1),
+/// )
+/// .unwrap();
+///
+/// assert_eq!(calendar.horizon(), TimeRange::new(0, 335));
+/// assert_eq!(calendar.slot_of(monday.and_hms_opt(9, 45, 0).unwrap()), Some(19));
+/// assert_eq!(
+///     calendar.start_of::<NaiveDateTime>(19),
+///     monday.and_hms_opt(9, 30, 0)
+/// );
+/// assert_eq!(calendar.format(&TimeRange::new(19, 20)), "Mon 09:30–10:30");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotCalendar<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    epoch: NaiveDateTime,
+    granularity: Duration,
+    last: N,
+}
+
+impl<N> SlotCalendar<N>
+where
+    N: Integer + One + Copy + Display + Debug + FromPrimitive + ToPrimitive,
+{
+    /// Constructs a calendar whose slots start at `epoch`, and continue
+    /// until `end` (exclusive). A trailing partial slot is not part of the horizon.
+    pub fn new(
+        epoch: impl CalendarTime,
+        granularity: Duration,
+        end: impl CalendarTime,
+    ) -> Result<SlotCalendar<N>, CalendarError> {
+        let epoch = epoch.to_naive();
+        let end = end.to_naive();
+
+        if granularity.num_milliseconds() < 1 {
+            return Err(CalendarError::InvalidGranularity(granularity));
+        }
+
+        let slots = (end - epoch).num_milliseconds() / granularity.num_milliseconds();
+        if slots < 1 {
+            return Err(CalendarError::EmptyHorizon { epoch, end });
+        }
+
+        Ok(SlotCalendar {
+            epoch,
+            granularity,
+            last: <N>::from_i64(slots - 1).ok_or(CalendarError::HorizonOverflow(slots))?,
+        })
+    }
+
+    pub fn epoch(&self) -> NaiveDateTime {
+        self.epoch
+    }
+
+    pub fn granularity(&self) -> Duration {
+        self.granularity
+    }
+
+    /// The first and last slot of this calendar
+    pub fn horizon(&self) -> TimeRange<N> {
+        TimeRange::new(<N>::zero(), self.last)
+    }
+
+    /// Every slot of this calendar, as a `Schedule::availability`
+    pub fn availability(&self) -> TimeSet<N> {
+        self.horizon().into()
+    }
+
+    /// Number of slots in a day, if a day is made up of whole slots
+    pub fn slots_per_day(&self) -> Option<N> {
+        let day = Duration::days(1).num_milliseconds();
+        let slot = self.granularity.num_milliseconds();
+
+        if day % slot == 0 {
+            <N>::from_i64(day / slot)
+        } else {
+            None
+        }
+    }
+
+    fn offset(&self, at: &impl CalendarTime) -> i64 {
+        (at.to_naive() - self.epoch).num_milliseconds()
+    }
+
+    /// Converts an (unbounded) slot index into the horizon,
+    /// clamping to the first or last slot.
+    fn clamp_slot(&self, slot: i64) -> N {
+        let last = self.last.to_i64().unwrap_or(i64::MAX);
+        <N>::from_i64(slot.clamp(0, last)).unwrap_or(self.last)
+    }
+
+    /// Slots in [first, last] limited to the horizon
+    fn bounded(&self, first: i64, last: i64) -> Option<TimeRange<N>> {
+        if last < first || last < 0 || first > self.last.to_i64()? {
+            None
+        } else {
+            Some(TimeRange::new(
+                self.clamp_slot(first),
+                self.clamp_slot(last),
+            ))
+        }
+    }
+
+    /// The slot which contains `at`, or `None` if `at` is outside of the horizon
+    pub fn slot_of(&self, at: impl CalendarTime) -> Option<N> {
+        let slot = self
+            .offset(&at)
+            .div_euclid(self.granularity.num_milliseconds());
+        self.bounded(slot, slot).map(|range| range.start)
+    }
+
+    /// The time at which `slot` starts
+    pub fn start_of<T: CalendarTime>(&self, slot: N) -> Option<T> {
+        T::from_naive(
+            self.epoch
+                .checked_add_signed(self.granularity.checked_mul(slot.to_i32()?)?)?,
+        )
+    }
+
+    /// The time at which `slot` ends. This is also the start of the following slot.
+    pub fn end_of<T: CalendarTime>(&self, slot: N) -> Option<T> {
+        T::from_naive(
+            self.epoch.checked_add_signed(
+                self.granularity
+                    .checked_mul(slot.to_i32()?.checked_add(1)?)?,
+            )?,
+        )
+    }
+
+    /// Slots which are (even partially) covered by [start, end).
+    ///
+    /// This rounds outwards, so that no busy time is ever treated as free.
+    /// Returns `None` if no slot within the horizon is touched.
+    ///
+    /// # Examples
+    /// ```
+    /// use chrono::{Duration, NaiveDate};
+    /// use zeitplan_libs::calendar::SlotCalendar;
+    /// use zeitplan_libs::time::TimeRange;
+    ///
+    /// let day = NaiveDate::from_ymd_opt(2022, 8, 1).unwrap();
+    /// let calendar: SlotCalendar<u8> =
+    ///     SlotCalendar::new(day.and_hms_opt(0, 0, 0).unwrap(), Duration::minutes(30), day.and_hms_opt(12, 0, 0).unwrap()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     calendar.blocked(day.and_hms_opt(9, 10, 0).unwrap(), day.and_hms_opt(10, 5, 0).unwrap()),
+    ///     Some(TimeRange::new(18, 20))
+    /// );
+    /// ```
+    pub fn blocked(
+        &self,
+        start: impl CalendarTime,
+        end: impl CalendarTime,
+    ) -> Option<TimeRange<N>> {
+        let granularity = self.granularity.num_milliseconds();
+        let (start, end) = (self.offset(&start), self.offset(&end));
+        if end <= start {
+            return None;
+        }
+
+        self.bounded(
+            start.div_euclid(granularity),
+            (end + granularity - 1).div_euclid(granularity) - 1,
+        )
+    }
+
+    /// Slots which are entirely within [start, end).
+    ///
+    /// This rounds inwards, so that no partially free slot is ever treated as free.
+    /// Returns `None` if no whole slot within the horizon fits.
+    ///
+    /// # Examples
+    /// ```
+    /// use chrono::{Duration, NaiveDate};
+    /// use zeitplan_libs::calendar::SlotCalendar;
+    /// use zeitplan_libs::time::TimeRange;
+    ///
+    /// let day = NaiveDate::from_ymd_opt(2022, 8, 1).unwrap();
+    /// let calendar: SlotCalendar<u8> =
+    ///     SlotCalendar::new(day.and_hms_opt(0, 0, 0).unwrap(), Duration::minutes(30), day.and_hms_opt(12, 0, 0).unwrap()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     calendar.available(day.and_hms_opt(9, 10, 0).unwrap(), day.and_hms_opt(10, 5, 0).unwrap()),
+    ///     Some(TimeRange::new(19, 19))
+    /// );
+    /// assert_eq!(
+    ///     calendar.available(day.and_hms_opt(9, 10, 0).unwrap(), day.and_hms_opt(9, 50, 0).unwrap()),
+    ///     None
+    /// );
+    /// ```
+    pub fn available(
+        &self,
+        start: impl CalendarTime,
+        end: impl CalendarTime,
+    ) -> Option<TimeRange<N>> {
+        let granularity = self.granularity.num_milliseconds();
+        let (start, end) = (self.offset(&start), self.offset(&end));
+
+        self.bounded(
+            (start + granularity - 1).div_euclid(granularity),
+            end.div_euclid(granularity) - 1,
+        )
+    }
+
+    /// Formats the slots of `time` as a wall-clock range, such as "Mon 09:30–10:30".
+    /// Ranges ending on a later day also name the day they end on.
+    pub fn format(&self, time: &TimeRange<N>) -> String {
+        match (
+            self.start_of::<NaiveDateTime>(time.start),
+            self.end_of::<NaiveDateTime>(time.end),
+        ) {
+            (Some(start), Some(end)) => format_naive(start, end),
+            _ => format!("{}", time),
+        }
+    }
+
+    /// Formats the time of a scheduled meeting, such as "Mon 09:30–10:30"
+    pub fn format_meeting(&self, meeting: &MeetingTime<N>) -> String {
+        self.format(&meeting.time)
+    }
+}
+
+pub(crate) fn format_naive(start: NaiveDateTime, end: NaiveDateTime) -> String {
+    let midnight = start.date().succ_opt().and_then(|d| d.and_hms_opt(0, 0, 0));
+
+    if start.date() == end.date() || Some(end) == midnight {
+        format!("{}–{}", start.format("%a %H:%M"), end.format("%H:%M"))
+    } else {
+        format!("{}–{}", start.format("%a %H:%M"), end.format("%a %H:%M"))
+    }
+}
@@ -0,0 +1,272 @@
+use crate::calendar::{format_naive, SlotCalendar};
+use crate::participant::Participant;
+use crate::schedule::MeetingTime;
+use crate::time::{TimeRange, TimeSet};
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, NaiveDateTime, NaiveTime, Offset, TimeZone, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
+use num::{FromPrimitive, Integer, One, ToPrimitive};
+use std::fmt::{Debug, Display};
+
+/// A [start, end) range of wall-clock times, in the time zone of whoever declared it
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalTimeRange {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+impl LocalTimeRange {
+    pub fn new(start: NaiveDateTime, end: NaiveDateTime) -> LocalTimeRange {
+        LocalTimeRange { start, end }
+    }
+}
+
+/// The local times a participant is willing to meet on the given `days`.
+/// An `end` at or before `start` finishes on the following day.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkingHours {
+    pub days: Vec<Weekday>,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl WorkingHours {
+    pub fn new(days: Vec<Weekday>, start: NaiveTime, end: NaiveTime) -> WorkingHours {
+        WorkingHours { days, start, end }
+    }
+}
+
+/// A participant whose times are given in their own time zone.
+///
+/// `blocked_times` and `working_hours` are wall-clock times within `time_zone`.
+/// Converting to a `Participant` places them in the UTC slot space of a `SlotCalendar`,
+/// following any daylight saving changes within the horizon.
+/// A participant without `working_hours` may meet at any time they are not blocked.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct LocalParticipant {
+    pub id: String,
+    #[cfg_attr(feature = "serde", serde(rename = "timeZone"))]
+    pub time_zone: Tz,
+    #[cfg_attr(feature = "serde", serde(rename = "blockedTimes", default))]
+    pub blocked_times: Vec<LocalTimeRange>,
+    #[cfg_attr(feature = "serde", serde(rename = "workingHours", default))]
+    pub working_hours: Vec<WorkingHours>,
+}
+
+/// Which instant to choose for a wall-clock time which happens twice
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Fold {
+    Earliest,
+    Latest,
+}
+
+/// Converts a wall-clock time within `time_zone` to UTC.
+///
+/// Times skipped by a daylight saving change are read with the offset from before
+/// the change, as RFC 5545 does. `2:30` on a day where clocks jump from 2:00 to 3:00
+/// is therefore the same instant as `3:30`.
+pub(crate) fn to_utc(time_zone: &Tz, local: NaiveDateTime, fold: Fold) -> NaiveDateTime {
+    match time_zone.from_local_datetime(&local) {
+        LocalResult::Single(time) => time.naive_utc(),
+        LocalResult::Ambiguous(earliest, latest) => match fold {
+            Fold::Earliest => earliest.naive_utc(),
+            Fold::Latest => latest.naive_utc(),
+        },
+        LocalResult::None => {
+            let before = time_zone
+                .offset_from_utc_datetime(&(local - Duration::days(1)))
+                .fix();
+            local - Duration::seconds(before.local_minus_utc() as i64)
+        }
+    }
+}
+
+impl LocalParticipant {
+    pub fn new(
+        id: &str,
+        time_zone: Tz,
+        blocked_times: Vec<LocalTimeRange>,
+        working_hours: Vec<WorkingHours>,
+    ) -> LocalParticipant {
+        LocalParticipant {
+            id: id.to_string(),
+            time_zone,
+            blocked_times,
+            working_hours,
+        }
+    }
+
+    /// Slots within the horizon of `calendar` covered by `working_hours`.
+    /// Slots only partially within working hours are not included.
+    pub fn working_slots<N>(&self, calendar: &SlotCalendar<N>) -> TimeSet<N>
+    where
+        N: Integer + One + Copy + Display + Debug + FromPrimitive + ToPrimitive,
+    {
+        let horizon = calendar.horizon();
+        let (first, last) = match (
+            calendar.start_of::<NaiveDateTime>(horizon.start),
+            calendar.end_of::<NaiveDateTime>(horizon.end),
+        ) {
+            (Some(first), Some(last)) => (
+                self.time_zone.from_utc_datetime(&first).date_naive(),
+                self.time_zone.from_utc_datetime(&last).date_naive(),
+            ),
+            _ => return TimeSet::new(),
+        };
+
+        // Starting a day early catches working hours which run past midnight
+        first
+            .pred_opt()
+            .unwrap_or(first)
+            .iter_days()
+            .take_while(|day| *day <= last)
+            .flat_map(|day| {
+                self.working_hours
+                    .iter()
+                    .filter(move |hours| hours.days.contains(&day.weekday()))
+                    .filter_map(move |hours| {
+                        let start = day.and_time(hours.start);
+                        let end = if hours.end <= hours.start {
+                            day.succ_opt()?.and_time(hours.end)
+                        } else {
+                            day.and_time(hours.end)
+                        };
+
+                        // Working hours are available times, so the narrowest reading is used
+                        calendar.available(
+                            to_utc(&self.time_zone, start, Fold::Latest),
+                            to_utc(&self.time_zone, end, Fold::Earliest),
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    /// Places this participant within the slots of `calendar`.
+    ///
+    /// Blocked times are rounded outwards to whole slots, and any time outside
+    /// of `working_hours` is blocked as well.
+    ///
+    /// # Examples
+    /// ```
+    /// use chrono::{Duration, NaiveDate, NaiveTime, Weekday};
+    /// use chrono_tz::America::New_York;
+    /// use zeitplan_libs::calendar::{LocalParticipant, SlotCalendar, WorkingHours};
+    /// use zeitplan_libs::time::TimeRange;
+    ///
+    /// // One hour slots over Saturday and Sunday, in UTC
+    /// let saturday = NaiveDate::from_ymd_opt(2022, 11, 5).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    /// let calendar: SlotCalendar<u8> =
+    ///     SlotCalendar::new(saturday, Duration::hours(1), saturday + Duration::days(2)).unwrap();
+    ///
+    /// let participant = LocalParticipant::new(
+    ///     "ny",
+    ///     New_York,
+    ///     vec![],
+    ///     vec![WorkingHours::new(
+    ///         vec![Weekday::Sat, Weekday::Sun],
+    ///         NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+    ///         NaiveTime::from_hms_opt(10, 0, 0).unwrap(),
+    ///     )],
+    /// )
+    /// .to_participant(&calendar);
+    ///
+    /// // 9:00 is 13:00 UTC on Saturday, but 14:00 UTC once daylight saving ends on Sunday
+    /// assert_eq!(
+    ///     participant.blocked_times.as_slice(),
+    ///     &[TimeRange::new(0, 12), TimeRange::new(14, 37), TimeRange::new(39, 47)]
+    /// );
+    /// ```
+    pub fn to_participant<N>(&self, calendar: &SlotCalendar<N>) -> Participant<N>
+    where
+        N: Integer + One + Copy + Display + Debug + FromPrimitive + ToPrimitive,
+    {
+        let mut blocked: TimeSet<N> = self
+            .blocked_times
+            .iter()
+            .filter_map(|time| {
+                calendar.blocked(
+                    to_utc(&self.time_zone, time.start, Fold::Earliest),
+                    to_utc(&self.time_zone, time.end, Fold::Latest),
+                )
+            })
+            .collect();
+
+        if !self.working_hours.is_empty() {
+            blocked = blocked.union(&self.working_slots(calendar).complement(calendar.horizon()));
+        }
+
+        Participant::new(&self.id, blocked)
+    }
+}
+
+/// The time of a scheduled meeting, as seen by one of its attendees
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalMeetingTime {
+    pub participant: String,
+    pub start: DateTime<Tz>,
+    pub end: DateTime<Tz>,
+}
+
+impl Display for LocalMeetingTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "{} {}",
+            format_naive(self.start.naive_local(), self.end.naive_local()),
+            self.start.format("%Z")
+        )
+    }
+}
+
+impl<N> SlotCalendar<N>
+where
+    N: Integer + One + Copy + Display + Debug + FromPrimitive + ToPrimitive,
+{
+    /// The start and end of `time`, within `time_zone`
+    pub fn local_range(
+        &self,
+        time: &TimeRange<N>,
+        time_zone: Tz,
+    ) -> Option<(DateTime<Tz>, DateTime<Tz>)> {
+        Some((
+            self.start_of::<DateTime<Utc>>(time.start)?
+                .with_timezone(&time_zone),
+            self.end_of::<DateTime<Utc>>(time.end)?
+                .with_timezone(&time_zone),
+        ))
+    }
+
+    /// Formats `time` as a wall-clock range within `time_zone`, such as "Mon 09:30–10:30"
+    pub fn format_in(&self, time: &TimeRange<N>, time_zone: Tz) -> String {
+        match self.local_range(time, time_zone) {
+            Some((start, end)) => format_naive(start.naive_local(), end.naive_local()),
+            None => format!("{}", time),
+        }
+    }
+
+    /// The time of `meeting` for each of the `attendees`, in their own time zone
+    pub fn local_times<'a>(
+        &self,
+        meeting: &MeetingTime<N>,
+        attendees: impl IntoIterator<Item = &'a LocalParticipant>,
+    ) -> Vec<LocalMeetingTime> {
+        attendees
+            .into_iter()
+            .filter_map(|attendee| {
+                let (start, end) = self.local_range(&meeting.time, attendee.time_zone)?;
+                Some(LocalMeetingTime {
+                    participant: attendee.id.clone(),
+                    start,
+                    end,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,111 @@
+use crate::time::TimeRange;
+use num::{Integer, One};
+use std::fmt::{Debug, Display};
+
+/// A relation which must hold between the times of two meetings, given by their ids
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Constraint<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    /// `first` is over before `second` starts
+    Before { first: String, second: String },
+    /// `first` starts after `second` is over
+    After { first: String, second: String },
+    /// `second` starts after `first`, with at least `gap` free slots in between
+    MinGap {
+        first: String,
+        second: String,
+        gap: N,
+    },
+    /// `second` starts after `first`, with at most `gap` free slots in between
+    MaxGap {
+        first: String,
+        second: String,
+        gap: N,
+    },
+    /// Both meetings start on the same day
+    SameDay { first: String, second: String },
+    /// The meetings start on different days
+    DifferentDay { first: String, second: String },
+}
+
+impl<N> Constraint<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    /// The ids of the two meetings this constraint relates
+    pub fn meetings(&self) -> (&str, &str) {
+        match self {
+            Constraint::Before { first, second }
+            | Constraint::After { first, second }
+            | Constraint::MinGap { first, second, .. }
+            | Constraint::MaxGap { first, second, .. }
+            | Constraint::SameDay { first, second }
+            | Constraint::DifferentDay { first, second } => (first, second),
+        }
+    }
+
+    /// Whether this constraint needs to know which day a slot is on
+    pub fn uses_days(&self) -> bool {
+        matches!(
+            self,
+            Constraint::SameDay { .. } | Constraint::DifferentDay { .. }
+        )
+    }
+
+    /// Whether placing the first meeting at `first` and the second at `second` satisfies
+    /// this constraint. Days are counted from slot 0, every `slots_per_day` slots.
+    /// ```
+    /// use zeitplan_libs::constraint::Constraint;
+    /// use zeitplan_libs::time::TimeRange;
+    ///
+    /// let retro = Constraint::MinGap {
+    ///     first: String::from("demo"),
+    ///     second: String::from("retro"),
+    ///     gap: 2,
+    /// };
+    ///
+    /// assert!(retro.holds(&TimeRange::new(0, 1), &TimeRange::new(4, 5), None));
+    /// assert!(!retro.holds(&TimeRange::new(0, 1), &TimeRange::new(3, 4), None));
+    ///
+    /// let apart = Constraint::DifferentDay {
+    ///     first: String::from("demo"),
+    ///     second: String::from("retro"),
+    /// };
+    ///
+    /// assert!(!apart.holds(&TimeRange::new(0, 1), &TimeRange::new(8, 9), Some(10)));
+    /// assert!(apart.holds(&TimeRange::new(0, 1), &TimeRange::new(10, 11), Some(10)));
+    /// ```
+    pub fn holds(
+        &self,
+        first: &TimeRange<N>,
+        second: &TimeRange<N>,
+        slots_per_day: Option<N>,
+    ) -> bool {
+        // Free slots between the end of `first` and the start of `second`, if in that order
+        let gap = || {
+            if first.end < second.start {
+                Some(second.start - first.end - <N>::one())
+            } else {
+                None
+            }
+        };
+        let same_day = || {
+            slots_per_day
+                .map(|day| first.start / day == second.start / day)
+                .unwrap_or(true)
+        };
+
+        match self {
+            Constraint::Before { .. } => first.end < second.start,
+            Constraint::After { .. } => second.end < first.start,
+            Constraint::MinGap { gap: min, .. } => matches!(gap(), Some(gap) if gap >= *min),
+            Constraint::MaxGap { gap: max, .. } => matches!(gap(), Some(gap) if gap <= *max),
+            Constraint::SameDay { .. } => same_day(),
+            Constraint::DifferentDay { .. } => !same_day(),
+        }
+    }
+}
@@ -0,0 +1,822 @@
+use crate::calendar::{to_utc, Fold, SlotCalendar};
+use crate::meeting::MeetingParticipants;
+use crate::participant::Participant;
+use crate::schedule::{MeetingTime, ScheduleResult};
+use crate::time::{parse_ical_datetime, Recurrence, RecurrenceError, RecurrenceRule, TimeSet};
+use chrono::{Duration, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+use log::debug;
+use num::{FromPrimitive, Integer, One, ToPrimitive};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Debug, Display};
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum IcsError {
+    #[error("Could not read calendar: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Line {0} is not a valid content line")]
+    InvalidLine(String),
+    #[error("{component} is not closed")]
+    Unterminated { component: String },
+    #[error("{component} is missing {property}")]
+    MissingProperty { component: String, property: String },
+    #[error("Invalid value for {property}: {value}")]
+    InvalidValue { property: String, value: String },
+    #[error(transparent)]
+    Recurrence(#[from] RecurrenceError),
+}
+
+/// How to read the busy times of a calendar
+#[derive(Debug, Clone)]
+pub struct IcsOptions {
+    /// The calendar owner's address. Events this attendee declined are skipped.
+    pub attendee: Option<String>,
+    /// Time zone of floating times, and of `TZID`s which are not IANA names
+    pub time_zone: Tz,
+}
+
+impl IcsOptions {
+    pub fn new(time_zone: Tz) -> IcsOptions {
+        IcsOptions {
+            attendee: None,
+            time_zone,
+        }
+    }
+
+    /// Skips events declined by `attendee`, given as `name@example.com` or `mailto:name@example.com`
+    pub fn attendee(mut self, attendee: &str) -> IcsOptions {
+        self.attendee = Some(strip_mailto(attendee).to_string());
+        self
+    }
+}
+
+impl Default for IcsOptions {
+    fn default() -> Self {
+        IcsOptions::new(Tz::UTC)
+    }
+}
+
+/// A content line, such as `DTSTART;TZID=Europe/Berlin:20220802T140000`
+#[derive(Debug)]
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn invalid(&self) -> IcsError {
+        IcsError::InvalidValue {
+            property: self.name.clone(),
+            value: self.value.clone(),
+        }
+    }
+}
+
+fn strip_mailto(address: &str) -> &str {
+    match address.get(..7) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("mailto:") => &address[7..],
+        _ => address,
+    }
+}
+
+/// Whether `id` reads as an email address, such as `name@example.com`
+fn is_address(id: &str) -> bool {
+    match id.split_once('@') {
+        Some((name, domain)) => {
+            !name.is_empty()
+                && domain.contains('.')
+                && !domain.contains('@')
+                && !id.contains(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+/// Joins lines folded onto the next line by a leading space or tab
+fn unfold(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    for line in ics.lines() {
+        match (
+            line.strip_prefix(|c| c == ' ' || c == '\t'),
+            lines.last_mut(),
+        ) {
+            (Some(folded), Some(last)) => last.push_str(folded),
+            _ if line.trim().is_empty() => {}
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Splits `line` at every `delimiter` outside of double quotes
+fn split_unquoted(line: &str, delimiter: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut quoted = false;
+    let mut start = 0;
+    for (i, c) in line.char_indices() {
+        if c == '"' {
+            quoted = !quoted;
+        } else if c == delimiter && !quoted {
+            parts.push(&line[start..i]);
+            start = i + c.len_utf8();
+        }
+    }
+    parts.push(&line[start..]);
+    parts
+}
+
+fn parse_property(line: &str) -> Result<Property, IcsError> {
+    let invalid = || IcsError::InvalidLine(line.to_string());
+
+    let mut quoted = false;
+    let colon = line
+        .char_indices()
+        .find(|(_, c)| {
+            if *c == '"' {
+                quoted = !quoted;
+            }
+            *c == ':' && !quoted
+        })
+        .map(|(i, _)| i)
+        .ok_or_else(invalid)?;
+
+    let mut head = split_unquoted(&line[..colon], ';').into_iter();
+    let name = head
+        .next()
+        .filter(|name| !name.is_empty())
+        .ok_or_else(invalid)?;
+    let params = head
+        .map(|param| {
+            let (name, value) = param.split_once('=').ok_or_else(invalid)?;
+            Ok((
+                name.to_ascii_uppercase(),
+                value.trim_matches('"').to_string(),
+            ))
+        })
+        .collect::<Result<Vec<_>, IcsError>>()?;
+
+    Ok(Property {
+        name: name.to_ascii_uppercase(),
+        params,
+        value: line[colon + 1..].to_string(),
+    })
+}
+
+/// Parses an RFC 5545 DURATION, such as `PT1H30M`, `P1D` or `P2W`
+fn parse_duration(value: &str) -> Option<Duration> {
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let value = value.strip_prefix('P')?;
+
+    let mut duration = Duration::zero();
+    let mut number = String::new();
+    let mut time = false;
+    for c in value.chars() {
+        match c {
+            '0'..='9' => {
+                number.push(c);
+                continue;
+            }
+            'T' if number.is_empty() && !time => {
+                time = true;
+                continue;
+            }
+            _ => {}
+        }
+
+        let amount = number.parse::<i64>().ok()?;
+        number.clear();
+        duration += match (c, time) {
+            ('W', false) => Duration::weeks(amount),
+            ('D', false) => Duration::days(amount),
+            ('H', true) => Duration::hours(amount),
+            ('M', true) => Duration::minutes(amount),
+            ('S', true) => Duration::seconds(amount),
+            _ => return None,
+        };
+    }
+
+    if !number.is_empty() {
+        return None;
+    }
+    Some(if negative { -duration } else { duration })
+}
+
+/// A date or date-time value, and the time zone it is a wall-clock time within
+#[derive(Debug, Clone, Copy)]
+struct Moment {
+    local: NaiveDateTime,
+    time_zone: Tz,
+    all_day: bool,
+}
+
+impl Moment {
+    fn to_utc(self, fold: Fold) -> NaiveDateTime {
+        to_utc(&self.time_zone, self.local, fold)
+    }
+
+    /// The same instant, as a wall-clock time within `time_zone`
+    fn local_in(self, time_zone: Tz) -> NaiveDateTime {
+        if self.time_zone == time_zone {
+            self.local
+        } else {
+            time_zone
+                .from_utc_datetime(&self.to_utc(Fold::Earliest))
+                .naive_local()
+        }
+    }
+}
+
+fn time_zone_of(property: &Property, options: &IcsOptions) -> Tz {
+    match property.param("TZID") {
+        Some(tzid) => tzid.trim_start_matches('/').parse().unwrap_or_else(|_| {
+            debug!(target: "Ics", "Unknown TZID {}, using {}", tzid, options.time_zone);
+            options.time_zone
+        }),
+        None => options.time_zone,
+    }
+}
+
+/// Reads every DATE or DATE-TIME of a (possibly comma separated) `property`
+fn parse_moments(property: &Property, options: &IcsOptions) -> Result<Vec<Moment>, IcsError> {
+    let time_zone = time_zone_of(property, options);
+    property
+        .value
+        .split(',')
+        .map(|value| {
+            Ok(Moment {
+                local: parse_ical_datetime(value).ok_or_else(|| property.invalid())?,
+                time_zone: if value.ends_with('Z') {
+                    Tz::UTC
+                } else {
+                    time_zone
+                },
+                all_day: property.param("VALUE") == Some("DATE") || !value.contains('T'),
+            })
+        })
+        .collect()
+}
+
+fn parse_moment(property: &Property, options: &IcsOptions) -> Result<Moment, IcsError> {
+    parse_moments(property, options)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| property.invalid())
+}
+
+/// A busy [start, end) time, with the rule it repeats by
+#[derive(Debug, Clone)]
+struct Busy {
+    uid: Option<String>,
+    start: Moment,
+    end: NaiveDateTime,
+    rule: Option<RecurrenceRule>,
+    exdates: Vec<NaiveDateTime>,
+}
+
+impl Busy {
+    fn blocked<N>(&self, calendar: &SlotCalendar<N>) -> TimeSet<N>
+    where
+        N: Integer + One + Copy + Display + Debug + FromPrimitive + ToPrimitive,
+    {
+        let time_zone = self.start.time_zone;
+        let blocked = |start, end| {
+            calendar.blocked(
+                to_utc(&time_zone, start, Fold::Earliest),
+                to_utc(&time_zone, end, Fold::Latest),
+            )
+        };
+
+        match &self.rule {
+            None => blocked(self.start.local, self.end).into_iter().collect(),
+            Some(rule) => {
+                let recurrence = Recurrence {
+                    start: self.start.local,
+                    duration: self.end - self.start.local,
+                    rule: rule.clone(),
+                    exdates: self.exdates.clone(),
+                };
+                let horizon_end = calendar.end_of::<NaiveDateTime>(calendar.horizon().end);
+                recurrence
+                    .occurrences()
+                    .take_while(|(start, _)| {
+                        horizon_end
+                            .map(|end| to_utc(&time_zone, *start, Fold::Earliest) < end)
+                            .unwrap_or(true)
+                    })
+                    .filter_map(|(start, end)| blocked(start, end))
+                    .collect()
+            }
+        }
+    }
+}
+
+/// The properties of a VEVENT which decide when, and whether, it is busy
+#[derive(Debug, Default)]
+struct Event {
+    uid: Option<String>,
+    start: Option<Property>,
+    end: Option<Property>,
+    duration: Option<Property>,
+    rule: Option<Property>,
+    exdates: Vec<Property>,
+    recurrence_id: Option<Property>,
+    free: bool,
+    declined: bool,
+}
+
+/// What an event contributes to the busy times
+enum Parsed {
+    Busy(Busy),
+    /// An occurrence of the recurring event `uid` which moved or no longer happens
+    Override {
+        uid: String,
+        occurrence: Moment,
+    },
+}
+
+impl Event {
+    fn add(&mut self, property: Property, options: &IcsOptions) {
+        match property.name.as_str() {
+            "UID" => self.uid = Some(property.value),
+            "DTSTART" => self.start = Some(property),
+            "DTEND" => self.end = Some(property),
+            "DURATION" => self.duration = Some(property),
+            "RRULE" => self.rule = Some(property),
+            "EXDATE" => self.exdates.push(property),
+            "RECURRENCE-ID" => self.recurrence_id = Some(property),
+            "TRANSP" => self.free |= property.value.eq_ignore_ascii_case("TRANSPARENT"),
+            "STATUS" => self.free |= property.value.eq_ignore_ascii_case("CANCELLED"),
+            "ATTENDEE" => {
+                self.declined |= options
+                    .attendee
+                    .as_deref()
+                    .map(|attendee| strip_mailto(&property.value).eq_ignore_ascii_case(attendee))
+                    .unwrap_or(false)
+                    && property
+                        .param("PARTSTAT")
+                        .map(|status| status.eq_ignore_ascii_case("DECLINED"))
+                        .unwrap_or(false)
+            }
+            _ => {}
+        }
+    }
+
+    fn parse(self, options: &IcsOptions) -> Result<Vec<Parsed>, IcsError> {
+        let mut parsed = vec![];
+
+        if let (Some(uid), Some(recurrence_id)) = (&self.uid, &self.recurrence_id) {
+            parsed.push(Parsed::Override {
+                uid: uid.clone(),
+                occurrence: parse_moment(recurrence_id, options)?,
+            });
+        }
+        if self.free || self.declined {
+            return Ok(parsed);
+        }
+
+        let start = parse_moment(
+            self.start
+                .as_ref()
+                .ok_or_else(|| IcsError::MissingProperty {
+                    component: "VEVENT".to_string(),
+                    property: "DTSTART".to_string(),
+                })?,
+            options,
+        )?;
+        let end = match (&self.end, &self.duration) {
+            (Some(end), _) => parse_moment(end, options)?.local_in(start.time_zone),
+            (None, Some(duration)) => {
+                start.local + parse_duration(&duration.value).ok_or_else(|| duration.invalid())?
+            }
+            // All-day events without an end last the day, timed events last no time at all
+            (None, None) if start.all_day => start.local + Duration::days(1),
+            (None, None) => start.local,
+        };
+
+        let rule = match &self.rule {
+            Some(property) => {
+                let mut rule: RecurrenceRule = property.value.parse()?;
+                // A UTC UNTIL is compared against the local start times of the event
+                let utc_until = property
+                    .value
+                    .split(';')
+                    .any(|part| part.starts_with("UNTIL=") && part.ends_with('Z'));
+                if utc_until {
+                    rule.until = rule
+                        .until
+                        .map(|until| start.time_zone.from_utc_datetime(&until).naive_local());
+                }
+                Some(rule)
+            }
+            None => None,
+        };
+
+        let mut exdates = vec![];
+        for exdate in &self.exdates {
+            exdates.extend(
+                parse_moments(exdate, options)?
+                    .into_iter()
+                    .map(|moment| moment.local_in(start.time_zone)),
+            );
+        }
+
+        parsed.push(Parsed::Busy(Busy {
+            uid: self.uid,
+            start,
+            end,
+            rule,
+            exdates,
+        }));
+        Ok(parsed)
+    }
+}
+
+/// Reads the FREEBUSY periods of a VFREEBUSY, such as `20220802T140000Z/PT1H`
+fn parse_free_busy(property: &Property, options: &IcsOptions) -> Result<Vec<Busy>, IcsError> {
+    let free = property
+        .param("FBTYPE")
+        .map(|kind| kind.eq_ignore_ascii_case("FREE"))
+        .unwrap_or(false);
+    if free {
+        return Ok(vec![]);
+    }
+
+    let time_zone = time_zone_of(property, options);
+    property
+        .value
+        .split(',')
+        .map(|period| {
+            let (start, end) = period.split_once('/').ok_or_else(|| property.invalid())?;
+            let start = Moment {
+                local: parse_ical_datetime(start).ok_or_else(|| property.invalid())?,
+                time_zone: if start.ends_with('Z') {
+                    Tz::UTC
+                } else {
+                    time_zone
+                },
+                all_day: false,
+            };
+            let end = match parse_duration(end) {
+                Some(duration) => start.local + duration,
+                None => Moment {
+                    local: parse_ical_datetime(end).ok_or_else(|| property.invalid())?,
+                    time_zone: if end.ends_with('Z') {
+                        Tz::UTC
+                    } else {
+                        time_zone
+                    },
+                    all_day: false,
+                }
+                .local_in(start.time_zone),
+            };
+            Ok(Busy {
+                uid: None,
+                start,
+                end,
+                rule: None,
+                exdates: vec![],
+            })
+        })
+        .collect()
+}
+
+/// Reads the busy times of every VEVENT and VFREEBUSY within `ics`
+fn parse_busy(ics: &str, options: &IcsOptions) -> Result<Vec<Busy>, IcsError> {
+    let mut components: Vec<String> = vec![];
+    let mut event: Option<Event> = None;
+    let mut busy = vec![];
+    let mut overrides = vec![];
+
+    for line in unfold(ics) {
+        let property = parse_property(&line)?;
+        match property.name.as_str() {
+            "BEGIN" => {
+                let component = property.value.to_ascii_uppercase();
+                if component == "VEVENT" {
+                    event = Some(Event::default());
+                }
+                components.push(component);
+            }
+            "END" => {
+                let component = property.value.to_ascii_uppercase();
+                if components.last() != Some(&component) {
+                    return Err(IcsError::InvalidValue {
+                        property: property.name,
+                        value: property.value,
+                    });
+                }
+                components.pop();
+                if component == "VEVENT" {
+                    if let Some(event) = event.take() {
+                        for parsed in event.parse(options)? {
+                            match parsed {
+                                Parsed::Busy(time) => busy.push(time),
+                                Parsed::Override { uid, occurrence } => {
+                                    overrides.push((uid, occurrence))
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            _ => match (components.last().map(String::as_str), event.as_mut()) {
+                // Properties of alarms and other nested components are not the event's
+                (Some("VEVENT"), Some(event)) => event.add(property, options),
+                (Some("VFREEBUSY"), _) if property.name == "FREEBUSY" => {
+                    busy.extend(parse_free_busy(&property, options)?)
+                }
+                _ => {}
+            },
+        }
+    }
+
+    if let Some(component) = components.pop() {
+        return Err(IcsError::Unterminated { component });
+    }
+
+    // Overridden occurrences are replaced by their own VEVENT
+    for (uid, occurrence) in overrides {
+        for time in busy
+            .iter_mut()
+            .filter(|time| time.rule.is_some() && time.uid.as_ref() == Some(&uid))
+        {
+            time.exdates.push(occurrence.local_in(time.start.time_zone));
+        }
+    }
+
+    Ok(busy)
+}
+
+/// The busy times of an iCalendar (.ics) document, within the horizon of `calendar`.
+///
+/// Every VEVENT is busy, unless it is `TRANSP:TRANSPARENT`, `STATUS:CANCELLED`, or declined
+/// by `options.attendee`. Recurring events are expanded, leaving out their `EXDATE`s and any
+/// occurrence replaced by a `RECURRENCE-ID`. FREEBUSY periods of a VFREEBUSY are busy unless
+/// they are `FBTYPE=FREE`. Times are rounded outwards to whole slots.
+///
+/// # Examples
+/// ```
+/// use chrono::{Duration, NaiveDate};
+/// use zeitplan_libs::calendar::SlotCalendar;
+/// use zeitplan_libs::ics::{busy_times, IcsOptions};
+/// use zeitplan_libs::time::TimeRange;
+///
+/// let monday = NaiveDate::from_ymd_opt(2022, 8, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+/// let calendar: SlotCalendar<u16> =
+///     SlotCalendar::new(monday, Duration::minutes(30), monday + Duration::days(7)).unwrap();
+///
+/// let ics = "BEGIN:VCALENDAR\r\n\
+///            BEGIN:VEVENT\r\n\
+///            UID:standup\r\n\
+///            DTSTART:20220801T090000Z\r\n\
+///            DURATION:PT15M\r\n\
+///            RRULE:FREQ=DAILY;COUNT=2\r\n\
+///            END:VEVENT\r\n\
+///            END:VCALENDAR\r\n";
+///
+/// assert_eq!(
+///     busy_times::<u16>(ics, &calendar, &IcsOptions::default()).unwrap().as_slice(),
+///     &[TimeRange::new(18, 18), TimeRange::new(66, 66)]
+/// );
+/// ```
+pub fn busy_times<N>(
+    ics: &str,
+    calendar: &SlotCalendar<N>,
+    options: &IcsOptions,
+) -> Result<TimeSet<N>, IcsError>
+where
+    N: Integer + One + Copy + Display + Debug + FromPrimitive + ToPrimitive,
+{
+    Ok(parse_busy(ics, options)?
+        .iter()
+        .flat_map(|time| time.blocked(calendar))
+        .collect())
+}
+
+impl<N> Participant<N>
+where
+    N: Integer + One + Copy + Display + Debug + FromPrimitive + ToPrimitive,
+{
+    /// A participant blocked by the busy times of an iCalendar document. See `busy_times`.
+    pub fn from_ics(
+        id: &str,
+        ics: &str,
+        calendar: &SlotCalendar<N>,
+        options: &IcsOptions,
+    ) -> Result<Participant<N>, IcsError> {
+        Ok(Participant::new(id, busy_times(ics, calendar, options)?))
+    }
+
+    /// A participant blocked by the busy times of the .ics file at `path`. See `busy_times`.
+    pub fn from_ics_file(
+        id: &str,
+        path: impl AsRef<Path>,
+        calendar: &SlotCalendar<N>,
+        options: &IcsOptions,
+    ) -> Result<Participant<N>, IcsError> {
+        Participant::from_ics(id, &std::fs::read_to_string(path)?, calendar, options)
+    }
+}
+
+/// Escapes a TEXT value, such as a SUMMARY
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Writes a content line, folding it into lines of at most 75 octets
+fn write_line(ics: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            ics.push_str("\r\n ");
+            width = 1;
+        }
+        ics.push(c);
+        width += c.len_utf8();
+    }
+    ics.push_str("\r\n");
+}
+
+fn format_utc(time: NaiveDateTime) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+impl<N> ScheduleResult<N>
+where
+    N: Integer + One + Copy + Display + Debug + FromPrimitive + ToPrimitive,
+{
+    /// Writes the scheduled meetings as an iCalendar (.ics) document, with one VEVENT per meeting.
+    ///
+    /// Slots are placed with `calendar`, in UTC. The UID of every event is `{id}@zeitplan`,
+    /// so exporting an updated schedule replaces the events of an earlier export.
+    /// ATTENDEE lines list the participants of the matching `meetings`, with their id
+    /// as the name, and as a `mailto:` address when it is an email address.
+    /// Optional participants are marked with `ROLE=OPT-PARTICIPANT`, and so is everyone
+    /// invited to a meeting with a `min_attendance`, as nobody is needed in particular.
+    /// `stamp` is the DTSTAMP of every event.
+    ///
+    /// # Examples
+    /// ```
+    /// use chrono::{Duration, NaiveDate};
+    /// use zeitplan_libs::calendar::SlotCalendar;
+    /// use zeitplan_libs::meeting::MeetingParticipants;
+    /// use zeitplan_libs::participant::Participant;
+    /// use zeitplan_libs::schedule::{MeetingTime, ScheduleResult};
+    /// use zeitplan_libs::time::TimeRange;
+    ///
+    /// let monday = NaiveDate::from_ymd_opt(2022, 8, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    /// let calendar: SlotCalendar<u8> =
+    ///     SlotCalendar::new(monday, Duration::hours(1), monday + Duration::days(1)).unwrap();
+    ///
+    /// let result = ScheduleResult {
+    ///     count: 1,
+    ///     results: vec![MeetingTime::new("review", TimeRange::new(9, 10))],
+    ///     indices: vec![0],
+    ///     cost: 0,
+    /// };
+    /// let meetings = vec![MeetingParticipants::new(
+    ///     "review",
+    ///     vec![Participant::new("ann@example.com", vec![])],
+    ///     2,
+    /// )];
+    ///
+    /// let ics = result.to_ics(&calendar, &meetings, monday);
+    /// assert!(ics.contains("UID:review@zeitplan\r\n"));
+    /// assert!(ics.contains("DTSTART:20220801T090000Z\r\nDTEND:20220801T110000Z\r\n"));
+    /// assert!(ics.contains("ATTENDEE;CN=\"ann@example.com\":mailto:ann@example.com\r\n"));
+    /// ```
+    pub fn to_ics(
+        &self,
+        calendar: &SlotCalendar<N>,
+        meetings: &[MeetingParticipants<N>],
+        stamp: NaiveDateTime,
+    ) -> String {
+        write_calendar(self.results.iter(), calendar, meetings, stamp)
+    }
+
+    /// One iCalendar document per participant, holding only the meetings they attend.
+    /// See `to_ics`.
+    pub fn to_ics_by_participant(
+        &self,
+        calendar: &SlotCalendar<N>,
+        meetings: &[MeetingParticipants<N>],
+        stamp: NaiveDateTime,
+    ) -> BTreeMap<String, String> {
+        let participants: BTreeSet<&str> = meetings
+            .iter()
+            .flat_map(|meeting| invitees(meeting).map(|(p, _)| p.id.as_str()))
+            .collect();
+
+        participants
+            .into_iter()
+            .map(|participant| {
+                let attending = self.results.iter().filter(|time| {
+                    meetings.iter().any(|meeting| {
+                        meeting.id == time.id && invitees(meeting).any(|(p, _)| p.id == participant)
+                    })
+                });
+                (
+                    participant.to_string(),
+                    write_calendar(attending, calendar, meetings, stamp),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Everyone invited to `meeting`, and whether they are optional
+fn invitees<N>(meeting: &MeetingParticipants<N>) -> impl Iterator<Item = (&Participant<N>, bool)>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    let quorum = meeting.min_attendance.is_some();
+    meeting
+        .participants
+        .iter()
+        .map(move |participant| (participant, quorum))
+        .chain(meeting.optional_participants.iter().map(|p| (p, true)))
+}
+
+fn write_calendar<'a, N>(
+    times: impl Iterator<Item = &'a MeetingTime<N>>,
+    calendar: &SlotCalendar<N>,
+    meetings: &[MeetingParticipants<N>],
+    stamp: NaiveDateTime,
+) -> String
+where
+    N: Integer + One + Copy + Display + Debug + FromPrimitive + ToPrimitive + 'a,
+{
+    let mut ics = String::new();
+    write_line(&mut ics, "BEGIN:VCALENDAR");
+    write_line(&mut ics, "VERSION:2.0");
+    write_line(&mut ics, "PRODID:-//Zeitplan//Zeitplan//EN");
+    write_line(&mut ics, "METHOD:PUBLISH");
+
+    for time in times {
+        let (start, end) = match (
+            calendar.start_of::<NaiveDateTime>(time.time.start),
+            calendar.end_of::<NaiveDateTime>(time.time.end),
+        ) {
+            (Some(start), Some(end)) => (start, end),
+            _ => {
+                debug!(target: "Ics", "Meeting {} is outside of the calendar", time.id);
+                continue;
+            }
+        };
+
+        write_line(&mut ics, "BEGIN:VEVENT");
+        write_line(&mut ics, &format!("UID:{}@zeitplan", escape_text(&time.id)));
+        write_line(&mut ics, &format!("DTSTAMP:{}", format_utc(stamp)));
+        write_line(&mut ics, &format!("DTSTART:{}", format_utc(start)));
+        write_line(&mut ics, &format!("DTEND:{}", format_utc(end)));
+        write_line(&mut ics, &format!("SUMMARY:{}", escape_text(&time.id)));
+        if !time.resources.is_empty() {
+            write_line(
+                &mut ics,
+                &format!("LOCATION:{}", escape_text(&time.resources.join(", "))),
+            );
+        }
+        for (participant, optional) in meetings
+            .iter()
+            .filter(|meeting| meeting.id == time.id)
+            .flat_map(invitees)
+        {
+            let role = if optional {
+                ";ROLE=OPT-PARTICIPANT"
+            } else {
+                ""
+            };
+            let name = participant.id.replace('"', "");
+            let address = strip_mailto(&participant.id);
+            write_line(
+                &mut ics,
+                &if is_address(address) {
+                    format!("ATTENDEE{};CN=\"{}\":mailto:{}", role, name, address)
+                } else {
+                    let id = escape_text(&participant.id);
+                    format!("ATTENDEE{};CN=\"{}\":{}", role, name, id)
+                },
+            );
+        }
+        write_line(&mut ics, "END:VEVENT");
+    }
+
+    write_line(&mut ics, "END:VCALENDAR");
+    ics
+}
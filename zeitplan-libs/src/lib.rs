@@ -5,15 +5,31 @@
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
+/// Maps slots to wall-clock times
+pub mod calendar;
+
+/// Reading busy times from, and writing schedules to, iCalendar files
+#[cfg(feature = "ics")]
+pub mod ics;
+
+/// Relations between the times of meetings, such as one before another
+pub mod constraint;
+
 /// Meetings to be scheduled
 pub mod meeting;
 
 /// Participants of meetings
 pub mod participant;
 
+/// Rooms and other resources booked by meetings
+pub mod resource;
+
 /// Holds the information for scheduling multiple meetings at once
 pub mod schedule;
 
+/// Ways of searching for a schedule, behind a common `Solver` trait
+pub mod solver;
+
 /// Utility functions for TimeRange. Used throughout the lib
 pub mod time;
 
@@ -35,10 +51,10 @@ pub fn get_meeting_availability(
     available_times: JsValue,
 ) -> Result<JsValue, JsValue> {
     use crate::meeting::Meeting;
-    use crate::time::{Available, TimeRange};
+    use crate::time::{Available, TimeSet};
 
     let meeting: Meeting<u16> = serde_wasm_bindgen::from_value(meeting)?;
-    let available_times: Vec<TimeRange<u16>> = serde_wasm_bindgen::from_value(available_times)?;
+    let available_times: TimeSet<u16> = serde_wasm_bindgen::from_value(available_times)?;
 
     Ok(serde_wasm_bindgen::to_value(
         &meeting.get_availability(&available_times),
@@ -52,10 +68,10 @@ pub fn get_participant_availability(
     available_times: JsValue,
 ) -> Result<JsValue, JsValue> {
     use crate::participant::Participant;
-    use crate::time::{Available, TimeRange};
+    use crate::time::{Available, TimeSet};
 
     let participant: Participant<u16> = serde_wasm_bindgen::from_value(participant)?;
-    let available_times: Vec<TimeRange<u16>> = serde_wasm_bindgen::from_value(available_times)?;
+    let available_times: TimeSet<u16> = serde_wasm_bindgen::from_value(available_times)?;
 
     Ok(serde_wasm_bindgen::to_value(
         &participant.get_availability(&available_times),
@@ -66,9 +82,10 @@ pub fn get_participant_availability(
 mod tests {
 
     #[test]
+    #[allow(clippy::needless_borrow)]
     fn gets_participant_availability() {
         use crate::participant::Participant;
-        use crate::time::{Available, TimeRange};
+        use crate::time::{Available, TimeRange, TimeSet};
 
         let blocked_times = vec![
             TimeRange::new(1, 2),
@@ -78,15 +95,16 @@ mod tests {
         ];
         let participant = Participant::new(&"1", blocked_times);
 
-        let available_time = vec![
+        let available_time: TimeSet<_> = vec![
             TimeRange::new(0, 9),
             TimeRange::new(22, 24),
             TimeRange::new(30, 35),
-        ];
+        ]
+        .into();
 
         assert_eq!(
-            participant.get_availability(&available_time),
-            vec![
+            participant.get_availability(&available_time).as_slice(),
+            &[
                 TimeRange::new(0, 0),
                 TimeRange::new(3, 3),
                 TimeRange::new(5, 9),
@@ -97,6 +115,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::useless_conversion)]
     fn merge_times() {
         use crate::time::{TimeMerge, TimeRange};
 
@@ -118,6 +137,518 @@ mod tests {
         );
     }
 
+    #[test]
+    fn time_set_algebra() {
+        use crate::time::{TimeRange, TimeSet};
+
+        let a: TimeSet<u8> = vec![
+            TimeRange::new(5, 9),
+            TimeRange::new(0, 2),
+            TimeRange::new(3, 3),
+            TimeRange::new(20, 255),
+        ]
+        .into();
+        let b: TimeSet<u8> = vec![TimeRange::new(2, 6), TimeRange::new(254, 255)].into();
+
+        assert_eq!(
+            a.as_slice(),
+            &[
+                TimeRange::new(0, 3),
+                TimeRange::new(5, 9),
+                TimeRange::new(20, 255)
+            ]
+        );
+        assert_eq!(
+            a.union(&b).as_slice(),
+            &[TimeRange::new(0, 9), TimeRange::new(20, 255)]
+        );
+        assert_eq!(
+            a.intersection(&b).as_slice(),
+            &[
+                TimeRange::new(2, 3),
+                TimeRange::new(5, 6),
+                TimeRange::new(254, 255)
+            ]
+        );
+        assert_eq!(
+            a.difference(&b).as_slice(),
+            &[
+                TimeRange::new(0, 1),
+                TimeRange::new(7, 9),
+                TimeRange::new(20, 253)
+            ]
+        );
+        assert_eq!(
+            a.complement(TimeRange::new(0, 255)).as_slice(),
+            &[TimeRange::new(4, 4), TimeRange::new(10, 19)]
+        );
+        assert!(a.contains(&TimeRange::new(20, 255)));
+        assert!(!a.contains(&TimeRange::new(3, 5)));
+        assert!(a.overlaps(&TimeRange::new(3, 5)));
+        assert!(!a.overlaps(&TimeRange::new(10, 19)));
+        assert_eq!(a.len_in_slots(), Some(245));
+        assert_eq!(
+            a.union(&a.complement(TimeRange::new(0, 255)))
+                .len_in_slots(),
+            None
+        );
+    }
+
+    #[test]
+    fn slot_calendar() {
+        use crate::calendar::SlotCalendar;
+        use crate::time::TimeRange;
+        use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime};
+
+        let monday = NaiveDate::from_ymd_opt(2022, 8, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let calendar: SlotCalendar<u8> =
+            SlotCalendar::new(monday, Duration::hours(1), monday + Duration::days(7)).unwrap();
+
+        assert_eq!(calendar.horizon(), TimeRange::new(0, 167));
+        assert_eq!(calendar.slots_per_day(), Some(24));
+        assert_eq!(
+            calendar.end_of::<NaiveDateTime>(33),
+            Some(monday + Duration::hours(34))
+        );
+        assert_eq!(calendar.slot_of(monday - Duration::seconds(1)), None);
+        assert_eq!(calendar.slot_of(monday + Duration::days(7)), None);
+
+        // 10:30 at UTC+02:00 is 08:30 UTC
+        let zoned =
+            DateTime::<FixedOffset>::parse_from_rfc3339("2022-08-01T10:30:00+02:00").unwrap();
+        assert_eq!(calendar.slot_of(zoned), Some(8));
+        assert_eq!(
+            calendar.start_of::<DateTime<FixedOffset>>(8).unwrap(),
+            zoned - Duration::minutes(30)
+        );
+
+        // Blocks reaching outside of the horizon are trimmed to it
+        assert_eq!(
+            calendar.blocked(monday - Duration::days(1), monday + Duration::minutes(1)),
+            Some(TimeRange::new(0, 0))
+        );
+        assert_eq!(
+            calendar.available(monday - Duration::days(1), monday + Duration::minutes(1)),
+            None
+        );
+        assert_eq!(
+            calendar.format(&TimeRange::new(23, 24)),
+            "Mon 23:00–Tue 01:00"
+        );
+        assert_eq!(calendar.format(&TimeRange::new(23, 23)), "Mon 23:00–00:00");
+
+        // 256 slots still fit within a u8
+        let full: SlotCalendar<u8> =
+            SlotCalendar::new(monday, Duration::hours(1), monday + Duration::hours(256)).unwrap();
+        assert_eq!(full.horizon(), TimeRange::new(0, 255));
+        assert!(
+            SlotCalendar::<u8>::new(monday, Duration::hours(1), monday + Duration::hours(257))
+                .is_err()
+        );
+
+        #[cfg(feature = "time")]
+        {
+            let start: ::time::OffsetDateTime = calendar.start_of(33).unwrap();
+            assert_eq!((start.date().day(), start.time().hour()), (2, 9));
+            assert_eq!(calendar.slot_of(start), Some(33));
+        }
+    }
+
+    #[cfg(feature = "tz")]
+    #[test]
+    fn local_time_zones() {
+        use crate::calendar::{LocalParticipant, LocalTimeRange, SlotCalendar};
+        use crate::schedule::MeetingTime;
+        use crate::time::TimeRange;
+        use chrono::{Duration, NaiveDate};
+        use chrono_tz::{America::New_York, Europe::Berlin};
+
+        // Half hour slots over the night clocks in Berlin jump from 2:00 to 3:00
+        let day = NaiveDate::from_ymd_opt(2022, 3, 27).unwrap();
+        let epoch = NaiveDate::from_ymd_opt(2022, 3, 26)
+            .unwrap()
+            .and_hms_opt(22, 0, 0)
+            .unwrap();
+        let calendar: SlotCalendar<u8> =
+            SlotCalendar::new(epoch, Duration::minutes(30), epoch + Duration::hours(12)).unwrap();
+
+        // 2:30 does not exist, and is read as 3:30 CEST (01:30 UTC)
+        let berlin = LocalParticipant::new(
+            "berlin",
+            Berlin,
+            vec![LocalTimeRange::new(
+                day.and_hms_opt(1, 0, 0).unwrap(),
+                day.and_hms_opt(2, 30, 0).unwrap(),
+            )],
+            vec![],
+        );
+        assert_eq!(
+            berlin.to_participant(&calendar).blocked_times.as_slice(),
+            &[TimeRange::new(4, 6)]
+        );
+
+        let new_york = LocalParticipant::new("new_york", New_York, vec![], vec![]);
+        let meeting = MeetingTime::new("1", TimeRange::new(20, 21));
+        let local = calendar
+            .local_times(&meeting, &[berlin, new_york])
+            .iter()
+            .map(|time| (time.participant.clone(), time.to_string()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            local,
+            vec![
+                ("berlin".to_string(), "Sun 10:00–11:00 CEST".to_string()),
+                ("new_york".to_string(), "Sun 04:00–05:00 EDT".to_string()),
+            ]
+        );
+        assert_eq!(
+            calendar.format_in(&TimeRange::new(0, 1), Berlin),
+            "Sat 23:00–00:00"
+        );
+    }
+
+    #[test]
+    fn recurrences() {
+        use crate::calendar::SlotCalendar;
+        use crate::meeting::Meeting;
+        use crate::schedule::Schedule;
+        use crate::time::{Recurrence, RecurrenceError, RecurrenceRule, TimeRange};
+        use chrono::{Duration, NaiveDate};
+
+        let day = |month, day, hour| {
+            NaiveDate::from_ymd_opt(2022, month, day)
+                .unwrap()
+                .and_hms_opt(hour, 0, 0)
+                .unwrap()
+        };
+
+        // Every other Tuesday and Thursday; the skipped Thursday still counts towards COUNT
+        let recurrence = Recurrence::new(
+            day(8, 2, 14),
+            Duration::hours(1),
+            "RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TH,TU;COUNT=4"
+                .parse()
+                .unwrap(),
+        )
+        .except(day(8, 4, 14));
+        assert_eq!(
+            recurrence
+                .occurrences()
+                .map(|(start, _)| start)
+                .collect::<Vec<_>>(),
+            vec![day(8, 2, 14), day(8, 16, 14), day(8, 18, 14)]
+        );
+
+        // The 31st only exists in some months
+        let monthly = Recurrence::new(
+            day(1, 31, 9),
+            Duration::hours(1),
+            "FREQ=MONTHLY;UNTIL=20220601T000000Z".parse().unwrap(),
+        );
+        assert_eq!(
+            monthly
+                .occurrences()
+                .map(|(start, _)| start)
+                .collect::<Vec<_>>(),
+            vec![day(1, 31, 9), day(3, 31, 9), day(5, 31, 9)]
+        );
+
+        // Stepping a week at a time from a Monday never lands on a Tuesday,
+        // with or without an UNTIL or a horizon to stop at
+        let never =
+            |rule: &str| Recurrence::new(day(8, 1, 9), Duration::hours(1), rule.parse().unwrap());
+        assert_eq!(
+            never("FREQ=DAILY;INTERVAL=7;BYDAY=TU;UNTIL=20220901T000000Z")
+                .occurrences()
+                .count(),
+            0
+        );
+        let calendar: SlotCalendar<u16> =
+            SlotCalendar::new(day(8, 1, 0), Duration::hours(1), day(8, 8, 0)).unwrap();
+        assert!(never("FREQ=DAILY;INTERVAL=14;BYDAY=TU")
+            .blocked_times(&calendar)
+            .is_empty());
+        assert_eq!(
+            never("FREQ=DAILY;INTERVAL=7;BYDAY=MO,TU;COUNT=2")
+                .occurrences()
+                .map(|(start, _)| start)
+                .collect::<Vec<_>>(),
+            vec![day(8, 1, 9), day(8, 8, 9)]
+        );
+
+        assert_eq!(
+            "FREQ=MONTHLY;BYDAY=1MO".parse::<RecurrenceRule>(),
+            Err(RecurrenceError::InvalidValue {
+                part: "BYDAY".to_string(),
+                value: "1MO".to_string()
+            })
+        );
+        assert_eq!(
+            "INTERVAL=2".parse::<RecurrenceRule>(),
+            Err(RecurrenceError::MissingFrequency)
+        );
+
+        // Office hours from 9:30 to 11:15 every day, as availability in half hour slots
+        let calendar: SlotCalendar<u16> =
+            SlotCalendar::new(day(8, 1, 0), Duration::minutes(30), day(8, 4, 0)).unwrap();
+        let office_hours = Recurrence::new(
+            day(8, 1, 9) + Duration::minutes(30),
+            Duration::minutes(105),
+            "FREQ=DAILY".parse().unwrap(),
+        );
+        let availability = office_hours.availability(&calendar);
+        assert_eq!(
+            availability.as_slice(),
+            &[
+                TimeRange::new(19, 21),
+                TimeRange::new(67, 69),
+                TimeRange::new(115, 117)
+            ]
+        );
+        assert_eq!(
+            office_hours.blocked_times(&calendar).as_slice(),
+            &[
+                TimeRange::new(19, 22),
+                TimeRange::new(67, 70),
+                TimeRange::new(115, 118)
+            ]
+        );
+
+        let schedule = Schedule::new(
+            vec![
+                Meeting::new("1", vec![], 3),
+                Meeting::new("2", vec![], 3),
+                Meeting::new("3", vec![], 3),
+            ],
+            availability,
+        );
+        assert!(schedule.schedule_meetings(None, None, None).is_ok());
+    }
+
+    #[cfg(feature = "ics")]
+    #[test]
+    fn ics_import() {
+        use crate::calendar::SlotCalendar;
+        use crate::ics::{busy_times, IcsError, IcsOptions};
+        use crate::participant::Participant;
+        use crate::time::TimeRange;
+        use chrono::{Duration, NaiveDate};
+
+        // Hourly slots for the week starting Monday, August 1st (UTC)
+        let monday = NaiveDate::from_ymd_opt(2022, 8, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let calendar: SlotCalendar<u8> =
+            SlotCalendar::new(monday, Duration::hours(1), monday + Duration::weeks(1)).unwrap();
+
+        let ics = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VTIMEZONE\r
+TZID:Europe/Berlin\r
+BEGIN:DAYLIGHT\r
+DTSTART:19700329T020000\r
+TZOFFSETFROM:+0100\r
+TZOFFSETTO:+0200\r
+END:DAYLIGHT\r
+END:VTIMEZONE\r
+BEGIN:VEVENT\r
+UID:berlin\r
+DTSTART;TZID=\"Europe/Ber\r
+ lin\":20220801T100000\r
+DTEND;TZID=Europe/Berlin:20220801T113000\r
+BEGIN:VALARM\r
+TRIGGER:-PT15M\r
+DTSTART:20220801T000000Z\r
+END:VALARM\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:transparent\r
+DTSTART:20220801T120000Z\r
+DURATION:PT1H\r
+TRANSP:TRANSPARENT\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:declined\r
+DTSTART:20220801T140000Z\r
+DURATION:PT1H\r
+ATTENDEE;CN=Bob;PARTSTAT=DECLINED:mailto:Bob@example.com\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:daily\r
+DTSTART:20220802T060000Z\r
+DURATION:PT1H\r
+RRULE:FREQ=DAILY;COUNT=4\r
+EXDATE:20220803T060000Z\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:daily\r
+RECURRENCE-ID:20220804T060000Z\r
+DTSTART:20220804T070000Z\r
+DURATION:PT30M\r
+END:VEVENT\r
+BEGIN:VFREEBUSY\r
+FREEBUSY;FBTYPE=FREE:20220806T000000Z/PT1H\r
+FREEBUSY:20220806T100000Z/PT2H,20220806T150000Z/20220806T153000Z\r
+END:VFREEBUSY\r
+END:VCALENDAR\r
+";
+
+        let bob = Participant::from_ics(
+            "bob",
+            ics,
+            &calendar,
+            &IcsOptions::default().attendee("mailto:bob@example.com"),
+        )
+        .unwrap();
+        assert_eq!(
+            bob.blocked_times.as_slice(),
+            &[
+                TimeRange::new(8, 9),
+                TimeRange::new(30, 30),
+                TimeRange::new(79, 79),
+                TimeRange::new(102, 102),
+                TimeRange::new(130, 131),
+                TimeRange::new(135, 135)
+            ]
+        );
+
+        // Without knowing who declined, the declined event is busy
+        assert!(busy_times(ics, &calendar, &IcsOptions::default())
+            .unwrap()
+            .contains(&TimeRange::new(14, 14)));
+
+        assert!(matches!(
+            busy_times(
+                "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\n",
+                &calendar,
+                &IcsOptions::default()
+            ),
+            Err(IcsError::Unterminated { component }) if component == "VEVENT"
+        ));
+    }
+
+    #[cfg(feature = "ics")]
+    #[test]
+    fn ics_export() {
+        use crate::calendar::SlotCalendar;
+        use crate::ics::{busy_times, IcsOptions};
+        use crate::meeting::MeetingParticipants;
+        use crate::participant::Participant;
+        use crate::schedule::Schedule;
+        use crate::time::{TimeRange, TimeSet};
+        use chrono::{Duration, NaiveDate};
+
+        let monday = NaiveDate::from_ymd_opt(2022, 8, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let calendar: SlotCalendar<u8> =
+            SlotCalendar::new(monday, Duration::minutes(30), monday + Duration::days(1)).unwrap();
+
+        let meetings = vec![
+            MeetingParticipants::new(
+                "planning; all hands",
+                vec![
+                    Participant::new("ann@example.com", vec![TimeRange::new(0, 17)]),
+                    Participant::new("bob@example.com", vec![]),
+                ],
+                2,
+            ),
+            MeetingParticipants::new("1:1", vec![Participant::new("ann@example.com", vec![])], 1),
+        ];
+        let result = Schedule::new(
+            meetings.iter().cloned().map(Into::into).collect(),
+            calendar.availability(),
+        )
+        .schedule_meetings(None, None, None)
+        .unwrap();
+
+        let ics = result.to_ics(&calendar, &meetings, monday);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("UID:planning\\; all hands@zeitplan\r\n"));
+        assert!(ics.contains("UID:1:1@zeitplan\r\n"));
+        assert!(ics.lines().all(|line| line.len() <= 75));
+        assert!(ics.contains("ATTENDEE;CN=\"ann@example.com\":mailto:ann@example.com\r\n"));
+
+        // Ids which are not addresses are only given as the name
+        let named = vec![MeetingParticipants::new(
+            "1:1",
+            vec![Participant::new("carl", vec![])],
+            1,
+        )];
+        let ics = result.to_ics(&calendar, &named, monday);
+        assert!(ics.contains("ATTENDEE;CN=\"carl\":carl\r\n"));
+        assert!(!ics.contains("mailto:"));
+
+        // Every participant's calendar reads back as the meetings they attend
+        let by_participant = result.to_ics_by_participant(&calendar, &meetings, monday);
+        assert_eq!(
+            by_participant.keys().collect::<Vec<_>>(),
+            vec!["ann@example.com", "bob@example.com"]
+        );
+        let read = |participant: &str| {
+            busy_times::<u8>(
+                &by_participant[participant],
+                &calendar,
+                &IcsOptions::default(),
+            )
+            .unwrap()
+        };
+        let scheduled = |ids: &[&str]| {
+            result
+                .results
+                .iter()
+                .filter(|time| ids.contains(&time.id.as_str()))
+                .map(|time| time.time)
+                .collect::<TimeSet<u8>>()
+        };
+        assert_eq!(
+            read("ann@example.com"),
+            scheduled(&["planning; all hands", "1:1"])
+        );
+        assert_eq!(read("bob@example.com"), scheduled(&["planning; all hands"]));
+
+        // Optional participants are invited as such, and at a quorum meeting so is everyone
+        let invited = vec![
+            meetings[0]
+                .clone()
+                .with_optional_participants(vec![Participant::new("dan@example.com", vec![])])
+                .with_min_attendance(2),
+            meetings[1]
+                .clone()
+                .with_optional_participants(vec![Participant::new("eve", vec![])]),
+        ];
+        let ics = result.to_ics(&calendar, &invited, monday);
+        assert_eq!(ics.matches("ROLE=OPT-PARTICIPANT").count(), 4);
+        assert!(ics.contains(
+            "ATTENDEE;ROLE=OPT-PARTICIPANT;CN=\"bob@example.com\":mailto:bob@example.com\r\n"
+        ));
+        assert!(ics.contains(
+            "ATTENDEE;ROLE=OPT-PARTICIPANT;CN=\"dan@example.com\":mailto:dan@example.com\r\n"
+        ));
+        assert!(ics.contains("ATTENDEE;ROLE=OPT-PARTICIPANT;CN=\"eve\":eve\r\n"));
+        assert!(ics.contains("ATTENDEE;CN=\"ann@example.com\":mailto:ann@example.com\r\n"));
+        assert_eq!(
+            result
+                .to_ics_by_participant(&calendar, &invited, monday)
+                .keys()
+                .collect::<Vec<_>>(),
+            vec![
+                "ann@example.com",
+                "bob@example.com",
+                "dan@example.com",
+                "eve"
+            ]
+        );
+    }
+
     #[test]
     fn pigeon_count() {
         use crate::time::{Pigeons, TimeRange};
@@ -128,10 +659,11 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_borrow)]
     fn gets_meeting_availability() {
         use crate::meeting::{Meeting, MeetingParticipants};
         use crate::participant::Participant;
-        use crate::time::{Available, TimeRange};
+        use crate::time::{Available, TimeRange, TimeSet};
 
         let blocked_times_1: Vec<TimeRange<u8>> = vec![
             TimeRange::new(1, 2),
@@ -150,15 +682,16 @@ mod tests {
 
         let meeting: Meeting<u8> = meeting.into();
 
-        let available_time = vec![
+        let available_time: TimeSet<_> = vec![
             TimeRange::new(0, 9),
             TimeRange::new(22, 24),
             TimeRange::new(30, 35),
-        ];
+        ]
+        .into();
 
         assert_eq!(
-            meeting.get_availability(&available_time),
-            vec![TimeRange::new(5, 9), TimeRange::new(32, 35)]
+            meeting.get_availability(&available_time).as_slice(),
+            &[TimeRange::new(5, 9), TimeRange::new(32, 35)]
         );
     }
 
@@ -284,4 +817,1190 @@ mod tests {
 
         assert!(schedule.schedule_meetings(None, None, None).is_err());
     }
+
+    #[test]
+    fn parallel_meetings() {
+        use crate::meeting::{Meeting, MeetingParticipants};
+        use crate::participant::Participant;
+        use crate::schedule::{Schedule, ValidationError};
+        use crate::time::TimeRange;
+
+        let meeting = |id: &str, participants: &[&str], duration| -> Meeting<u8> {
+            MeetingParticipants::new(
+                id,
+                participants
+                    .iter()
+                    .map(|p| Participant::new(p, vec![]))
+                    .collect(),
+                duration,
+            )
+            .into()
+        };
+
+        // Nobody attends two of these, so they can all take place at once
+        let schedule = Schedule::new(
+            vec![
+                meeting("1", &["ann", "bob"], 2),
+                meeting("2", &["carl"], 2),
+                meeting("3", &["dave"], 2),
+            ],
+            vec![TimeRange::new(0, 1)],
+        );
+        let result = schedule.schedule_meetings(None, None, None).unwrap();
+        assert_eq!(result.results.len(), 3);
+        assert!(result
+            .results
+            .iter()
+            .all(|meeting| meeting.time == TimeRange::new(0, 1)));
+
+        // Unless only two meetings may run at the same time
+        assert_eq!(
+            schedule
+                .limit_concurrent(2)
+                .schedule_meetings(None, None, None)
+                .unwrap_err(),
+            ValidationError::PigeonholeError {
+                pigeons: 6,
+                pigeon_holes: 2
+            }
+        );
+
+        // Meetings sharing a participant, or without any, still take turns
+        let schedule = Schedule::new(
+            vec![
+                meeting("1", &["ann", "bob"], 1),
+                meeting("2", &["bob"], 1),
+                meeting("3", &["carl"], 1),
+                Meeting::new("4", vec![], 1),
+            ],
+            vec![TimeRange::new(0, 2)],
+        );
+        let result = schedule.schedule_meetings(None, None, None).unwrap();
+        let time_of = |id: &str| {
+            result
+                .results
+                .iter()
+                .find(|meeting| meeting.id == id)
+                .unwrap()
+                .time
+        };
+        assert_ne!(time_of("1"), time_of("2"));
+        assert!(["1", "2", "3"].iter().all(|id| time_of(id) != time_of("4")));
+
+        assert_eq!(
+            Schedule::new(
+                vec![meeting("1", &["ann"], 2), meeting("2", &["ann"], 2)],
+                vec![TimeRange::new(0, 2)],
+            )
+            .schedule_meetings(None, None, None)
+            .unwrap_err(),
+            ValidationError::PigeonholeError {
+                pigeons: 4,
+                pigeon_holes: 3
+            }
+        );
+    }
+
+    #[test]
+    fn resources() {
+        use crate::meeting::Meeting;
+        use crate::resource::{Resource, ResourceRequest};
+        use crate::schedule::{Schedule, ValidationError};
+        use crate::time::TimeRange;
+
+        let rooms = vec![
+            Resource::new("small", "room", vec![TimeRange::new(0, 3)]),
+            Resource::new("big", "room", vec![TimeRange::new(2, 5)]),
+            Resource::new("projector", "projector", vec![TimeRange::new(0, 5)]),
+        ];
+        let meeting = |id: &str, resources: Vec<ResourceRequest>| -> Meeting<u8> {
+            Meeting::new(id, vec![], 2)
+                .with_participants(vec![id.to_string()])
+                .with_resources(resources)
+        };
+        let room = || ResourceRequest::Kind("room".to_string());
+        let small = || ResourceRequest::OneOf(vec!["small".to_string()]);
+
+        let schedule = Schedule::new(
+            vec![
+                meeting(
+                    "demo",
+                    vec![room(), ResourceRequest::Kind("projector".to_string())],
+                ),
+                meeting("1:1", vec![small()]),
+                meeting("sync", vec![room()]),
+            ],
+            vec![TimeRange::new(0, 5)],
+        )
+        .with_resources(rooms.clone());
+
+        let result = schedule.schedule_meetings(None, None, None).unwrap();
+        assert_eq!(result.results.len(), 3);
+        for meeting in &result.results {
+            assert!(meeting.resources.iter().all(|id| rooms
+                .iter()
+                .any(|room| room.id == *id && room.availability.contains(&meeting.time))));
+            assert!(result.results.iter().all(|other| other.id == meeting.id
+                || other.time.end < meeting.time.start
+                || meeting.time.end < other.time.start
+                || other
+                    .resources
+                    .iter()
+                    .all(|r| !meeting.resources.contains(r))));
+        }
+        let demo = result.results.iter().find(|m| m.id == "demo").unwrap();
+        assert_eq!(demo.resources.len(), 2);
+        assert_eq!(demo.resources[1], "projector");
+
+        // Only four slots of the small room exist
+        assert_eq!(
+            Schedule::new(
+                vec![
+                    meeting("1", vec![small()]),
+                    meeting("2", vec![small()]),
+                    meeting("3", vec![small()]),
+                ],
+                vec![TimeRange::new(0, 5)],
+            )
+            .with_resources(rooms.clone())
+            .schedule_meetings(None, None, None)
+            .unwrap_err(),
+            ValidationError::PigeonholeError {
+                pigeons: 6,
+                pigeon_holes: 4
+            }
+        );
+
+        // Unless two meetings fit in it at once
+        let mut shared = rooms.clone();
+        shared[0] = shared[0].clone().with_capacity(2);
+        assert!(Schedule::new(
+            vec![
+                meeting("1", vec![small()]),
+                meeting("2", vec![small()]),
+                meeting("3", vec![small()]),
+            ],
+            vec![TimeRange::new(0, 5)],
+        )
+        .with_resources(shared)
+        .schedule_meetings(None, None, None)
+        .is_ok());
+
+        assert!(matches!(
+            Schedule::new(
+                vec![meeting(
+                    "1",
+                    vec![ResourceRequest::Kind("boat".to_string())]
+                )],
+                vec![TimeRange::new(0, 5)],
+            )
+            .with_resources(rooms)
+            .schedule_meetings(None, None, None),
+            Err(ValidationError::InvalidData { .. })
+        ));
+    }
+
+    #[test]
+    fn optional_participants() {
+        use crate::meeting::{Meeting, MeetingParticipants};
+        use crate::participant::Participant;
+        use crate::schedule::Schedule;
+        use crate::time::TimeRange;
+
+        let review: Meeting<u8> = MeetingParticipants::new(
+            "review",
+            vec![Participant::new("ann", vec![TimeRange::new(0, 0)])],
+            2,
+        )
+        .with_optional_participants(vec![
+            Participant::new("bob", vec![TimeRange::new(1, 3)]),
+            Participant::new("carl", vec![TimeRange::new(4, 5)]),
+            Participant::new("dave", vec![TimeRange::new(2, 2)]),
+        ])
+        .into();
+
+        // Only ann is needed, but bob and dave can both make it at the end
+        let result = Schedule::new(vec![review.clone()], vec![TimeRange::new(0, 5)])
+            .schedule_meetings(None, None, None)
+            .unwrap();
+        assert_eq!(result.cost, 1);
+        assert_eq!(result.results[0].time, TimeRange::new(4, 5));
+        assert_eq!(result.results[0].attending, vec!["bob", "dave"]);
+        assert_eq!(result.results[0].missing, vec!["carl"]);
+
+        // Running out of iterations still gives the best placement found so far
+        let result = Schedule::new(vec![review], vec![TimeRange::new(0, 5)])
+            .schedule_meetings(Some(1), None, None)
+            .unwrap();
+        assert_eq!(result.results[0].time, TimeRange::new(1, 2));
+        assert_eq!(result.cost, 2);
+
+        // An optional attendee can not make a meeting while attending another one
+        let schedule = Schedule::new(
+            vec![
+                MeetingParticipants::new("review", vec![Participant::new("ann", vec![])], 2)
+                    .with_optional_participants(vec![Participant::new("bob", vec![])])
+                    .into(),
+                MeetingParticipants::new("standup", vec![Participant::new("bob", vec![])], 2)
+                    .into(),
+            ],
+            vec![TimeRange::new(0, 1)],
+        );
+        let result = schedule.schedule_meetings(None, None, None).unwrap();
+        let review = result.results.iter().find(|m| m.id == "review").unwrap();
+        assert!(review.attending.is_empty());
+        assert_eq!(review.missing, vec!["bob"]);
+        assert_eq!(result.cost, 1);
+
+        // Given the room, the meetings are kept apart so that bob can make both
+        let result = Schedule::new(schedule.meetings, vec![TimeRange::new(0, 3)])
+            .schedule_meetings(None, None, None)
+            .unwrap();
+        let review = result.results.iter().find(|m| m.id == "review").unwrap();
+        assert_eq!(review.attending, vec!["bob"]);
+        assert_eq!(result.cost, 0);
+    }
+
+    #[test]
+    fn quorum_meetings() {
+        use crate::meeting::{Meeting, MeetingParticipants};
+        use crate::participant::{Participant, Preference};
+        use crate::schedule::Schedule;
+        use crate::time::TimeRange;
+
+        let board = MeetingParticipants::new(
+            "board",
+            vec![
+                Participant::new("ann", vec![TimeRange::new(0, 3)]),
+                Participant::new("bob", vec![TimeRange::new(2, 5)]),
+                Participant::new("cid", vec![TimeRange::new(4, 7)]),
+            ],
+            2,
+        )
+        .with_min_attendance(2);
+
+        // Two of the three are enough
+        let result = Schedule::new(vec![board.clone().into()], vec![TimeRange::new(0, 7)])
+            .schedule_meetings(None, None, None)
+            .unwrap();
+        assert_eq!(result.results[0].time, TimeRange::new(0, 1));
+        assert_eq!(result.results[0].attending, vec!["bob", "cid"]);
+        assert_eq!(result.results[0].missing, vec!["ann"]);
+
+        // Once bob and cid are busy with another meeting, ann and bob make the quorum
+        let sync = MeetingParticipants::new("sync", board.participants[1..].to_vec(), 2);
+        let result = Schedule::new(
+            vec![board.clone().into(), sync.into()],
+            vec![TimeRange::new(0, 7)],
+        )
+        .schedule_meetings(None, None, None)
+        .unwrap();
+        assert_eq!(result.results[0].id, "sync");
+        assert_eq!(result.results[0].time, TimeRange::new(0, 1));
+        assert_eq!(result.results[1].id, "board");
+        assert_eq!(result.results[1].time, TimeRange::new(6, 7));
+        assert_eq!(result.results[1].attending, vec!["ann", "bob"]);
+        assert_eq!(result.results[1].missing, vec!["cid"]);
+
+        // The limits and preferences of the members are kept, the same as without a quorum
+        let mut members = board.participants.clone();
+        members[0] = members[0].clone().with_max_meetings_per_day(1);
+        members[2] =
+            members[2]
+                .clone()
+                .with_preference(Preference::Avoid, vec![TimeRange::new(0, 1)], 5);
+        let picky: Meeting<u8> = MeetingParticipants::new("board", members, 2)
+            .with_min_attendance(2)
+            .into();
+        assert_eq!(
+            picky.limits.get("ann").and_then(|l| l.max_meetings_per_day),
+            Some(1)
+        );
+        assert_eq!(picky.preferences.len(), 1);
+        let result = Schedule::new(vec![picky], vec![TimeRange::new(0, 7)])
+            .with_slots_per_day(8)
+            .schedule_meetings(None, None, None)
+            .unwrap();
+        assert_eq!(result.results[0].time, TimeRange::new(6, 7));
+        assert_eq!(result.cost, 1);
+
+        // A quorum larger than the meeting can never be met
+        assert!(Schedule::new(
+            vec![board.with_min_attendance(4).into()],
+            vec![TimeRange::new(0, 7)]
+        )
+        .schedule_meetings(None, None, None)
+        .is_err());
+    }
+
+    #[test]
+    fn slot_preferences() {
+        use crate::meeting::MeetingParticipants;
+        use crate::participant::{Participant, Preference};
+        use crate::schedule::Schedule;
+        use crate::time::TimeRange;
+
+        let ann = Participant::new("ann", vec![]).with_preference(
+            Preference::Avoid,
+            vec![TimeRange::new(4, 5)],
+            5,
+        );
+        let bob = Participant::new("bob", vec![])
+            .with_preference(Preference::Preferred, vec![TimeRange::new(2, 5)], 1)
+            .with_preference(Preference::Neutral, vec![TimeRange::new(0, 5)], 9);
+
+        // The first time which suits everyone is chosen, rather than the first free time
+        let retro = MeetingParticipants::new("retro", vec![ann, bob.clone()], 2);
+        let result = Schedule::new(vec![retro.into()], vec![TimeRange::new(0, 5)])
+            .schedule_meetings(None, None, None)
+            .unwrap();
+        assert_eq!(result.results[0].time, TimeRange::new(2, 3));
+        assert_eq!(result.cost, 0);
+
+        // Heavier preferences win out
+        let ann = Participant::new("ann", vec![]).with_preference(
+            Preference::Avoid,
+            vec![TimeRange::new(2, 3)],
+            3,
+        );
+        let retro = MeetingParticipants::new("retro", vec![ann, bob], 2);
+        let result = Schedule::new(vec![retro.into()], vec![TimeRange::new(0, 5)])
+            .schedule_meetings(None, None, None)
+            .unwrap();
+        assert_eq!(result.results[0].time, TimeRange::new(4, 5));
+        assert_eq!(result.cost, 0);
+    }
+
+    #[test]
+    fn release_times_and_deadlines() {
+        use crate::meeting::Meeting;
+        use crate::schedule::{Schedule, ValidationError};
+        use crate::time::{TimeRange, TimeSet};
+
+        let meetings = vec![
+            Meeting::new("launch", TimeSet::new(), 2).with_earliest_start(4),
+            Meeting::new("freeze", TimeSet::new(), 2).with_latest_end(3),
+        ];
+        let result = Schedule::new(meetings.clone(), vec![TimeRange::new(0, 7)])
+            .schedule_meetings(None, None, None)
+            .unwrap();
+        let launch = result.results.iter().find(|m| m.id == "launch").unwrap();
+        let freeze = result.results.iter().find(|m| m.id == "freeze").unwrap();
+        assert!(launch.time.start >= 4);
+        assert!(freeze.time.end <= 3);
+
+        // Bounds which only leave single free slots have no room for the meeting
+        let meetings = vec![Meeting::new("late", TimeSet::new(), 2)
+            .with_earliest_start(2)
+            .with_latest_end(5)];
+        assert_eq!(
+            Schedule::new(meetings, vec![TimeRange::new(0, 2), TimeRange::new(5, 7)])
+                .setup()
+                .err(),
+            Some(ValidationError::OutOfBounds {
+                meeting: String::from("late")
+            })
+        );
+    }
+
+    #[test]
+    fn meeting_series() {
+        use crate::meeting::{Meeting, MeetingParticipants, MeetingSeries};
+        use crate::participant::Participant;
+        use crate::schedule::{Schedule, ValidationError};
+        use crate::time::TimeRange;
+
+        // Three days of ten slots, where ann is busy early on the first day
+        // and in the middle of the second
+        let standup: Meeting<u8> = MeetingParticipants::new(
+            "standup",
+            vec![Participant::new(
+                "ann",
+                vec![TimeRange::new(0, 3), TimeRange::new(15, 16)],
+            )],
+            2,
+        )
+        .into();
+        let times = |series: MeetingSeries<u8>| {
+            let mut result = Schedule::new(vec![], vec![TimeRange::new(0, 29)])
+                .with_series(series)
+                .unwrap()
+                .schedule_meetings(None, None, None)
+                .unwrap()
+                .results;
+            result.sort_by(|a, b| a.id.cmp(&b.id));
+            result.iter().map(|m| m.time.start).collect::<Vec<_>>()
+        };
+
+        // Every day at the same time
+        assert_eq!(
+            times(MeetingSeries::new(standup.clone(), 10, 3)),
+            vec![7, 17, 27]
+        );
+
+        // Up to a slot either way
+        assert_eq!(
+            times(MeetingSeries::new(standup.clone(), 10, 3).with_max_deviation(1)),
+            vec![4, 12, 22]
+        );
+
+        // A deviation whose slack overflows the slot type lets occurrences go anywhere
+        assert_eq!(
+            times(MeetingSeries::new(standup.clone(), 10, 3).with_max_deviation(200)),
+            vec![4, 6, 8]
+        );
+
+        // Pinned occurrences keep to the same time each day
+        let pinned = standup.clone().with_fixed_time(TimeRange::new(8, 9));
+        assert_eq!(times(MeetingSeries::new(pinned, 10, 3)), vec![8, 18, 28]);
+
+        // Occurrences past the last slot are turned down rather than wrapping around
+        assert!(matches!(
+            Schedule::new(vec![], vec![TimeRange::new(0, 29)])
+                .with_series(MeetingSeries::new(standup, 24, 12)),
+            Err(ValidationError::InvalidData { .. })
+        ));
+    }
+
+    #[test]
+    fn flexible_duration() {
+        use crate::meeting::Meeting;
+        use crate::schedule::Schedule;
+        use crate::time::{TimeRange, TimeSet};
+
+        let meetings = vec![
+            Meeting::new("workshop", TimeSet::new(), 2).with_duration_range(2, 4),
+            Meeting::new("standup", TimeSet::new(), 2),
+        ];
+        let length = |end: u8| {
+            let result = Schedule::new(meetings.clone(), vec![TimeRange::new(0, end)])
+                .schedule_meetings(None, None, None)
+                .unwrap();
+            let workshop = result.results.iter().find(|m| m.id == "workshop").unwrap();
+            (workshop.time.end - workshop.time.start + 1, result.cost)
+        };
+
+        // The workshop grows to fill the free time, up to its longest
+        assert_eq!(length(9), (4, 0));
+        assert_eq!(length(4), (3, 1));
+        // Only the shortest workshop has to fit
+        assert_eq!(length(3), (2, 2));
+    }
+
+    #[test]
+    fn constraints() {
+        use crate::constraint::Constraint;
+        use crate::meeting::Meeting;
+        use crate::schedule::Schedule;
+        use crate::time::{TimeRange, TimeSet};
+
+        let meetings = vec![
+            Meeting::new("design review", TimeSet::new(), 2),
+            Meeting::new("kickoff", TimeSet::new(), 2),
+            Meeting::new("demo", TimeSet::new(), 2),
+            Meeting::new("retro", TimeSet::new(), 2),
+        ];
+        let constraints = vec![
+            Constraint::Before {
+                first: String::from("kickoff"),
+                second: String::from("design review"),
+            },
+            Constraint::MinGap {
+                first: String::from("demo"),
+                second: String::from("retro"),
+                gap: 10,
+            },
+            Constraint::DifferentDay {
+                first: String::from("kickoff"),
+                second: String::from("demo"),
+            },
+        ];
+        let result = Schedule::new(meetings.clone(), vec![TimeRange::new(0, 29)])
+            .with_constraints(constraints.clone())
+            .with_slots_per_day(10)
+            .schedule_meetings(None, None, None)
+            .unwrap();
+        let time = |id: &str| result.results.iter().find(|m| m.id == id).unwrap().time;
+        assert!(time("kickoff").end < time("design review").start);
+        assert!(time("retro").start > time("demo").end + 10);
+        assert_ne!(time("kickoff").start / 10, time("demo").start / 10);
+
+        // Days can only be told apart once their length is known
+        assert!(Schedule::new(meetings, vec![TimeRange::new(0, 29)])
+            .with_constraints(constraints)
+            .setup()
+            .is_err());
+    }
+
+    #[test]
+    fn pinned_meetings() {
+        use crate::meeting::{Meeting, MeetingParticipants};
+        use crate::participant::Participant;
+        use crate::schedule::{Schedule, ValidationError};
+        use crate::time::TimeRange;
+
+        let ann = Participant::new("ann", vec![TimeRange::new(6, 7)]);
+        let confirmed: Meeting<u8> =
+            MeetingParticipants::new("confirmed", vec![ann.clone()], 2).into();
+        let meetings = vec![
+            confirmed.clone().with_fixed_time(TimeRange::new(2, 3)),
+            MeetingParticipants::new("follow up", vec![ann.clone()], 2).into(),
+            MeetingParticipants::new("elsewhere", vec![Participant::new("bob", vec![])], 2).into(),
+        ];
+        let result = Schedule::new(meetings, vec![TimeRange::new(0, 7)])
+            .schedule_meetings(None, None, None)
+            .unwrap();
+        let time = |id: &str| result.results.iter().find(|m| m.id == id).unwrap().time;
+        assert_eq!(time("confirmed"), TimeRange::new(2, 3));
+        assert_eq!(time("follow up"), TimeRange::new(0, 1));
+        // Only meetings sharing a participant have to move out of the way
+        assert_eq!(time("elsewhere"), TimeRange::new(0, 1));
+
+        // ann is busy at the pinned time
+        let meetings = vec![confirmed.with_fixed_time(TimeRange::new(5, 6))];
+        assert_eq!(
+            Schedule::new(meetings, vec![TimeRange::new(0, 7)])
+                .setup()
+                .err(),
+            Some(ValidationError::FixedTimeUnavailable {
+                meeting: String::from("confirmed")
+            })
+        );
+    }
+
+    #[test]
+    fn reschedule() {
+        use crate::meeting::Meeting;
+        use crate::schedule::{Disruption, MeetingTime, Schedule, ScheduleResult};
+        use crate::solver::{Auto, Backtracking};
+        use crate::time::{TimeRange, TimeSet};
+
+        let previous: ScheduleResult<u8> = ScheduleResult {
+            count: 1,
+            results: vec![
+                MeetingTime::new("a", TimeRange::new(0, 1)),
+                MeetingTime::new("b", TimeRange::new(2, 3)),
+                MeetingTime::new("c", TimeRange::new(6, 7)),
+            ],
+            indices: vec![0, 0, 0],
+            cost: 0,
+        };
+        // a can no longer start at 0, c was cancelled and d is new
+        let changed = Schedule::new(
+            vec![
+                Meeting::new("a", vec![TimeRange::new(0, 0)], 2),
+                Meeting::new("b", TimeSet::new(), 2),
+                Meeting::new("d", TimeSet::new(), 1),
+            ],
+            vec![TimeRange::new(0, 7)],
+        );
+
+        let rescheduled = changed
+            .reschedule(&previous, Disruption::FewestMoves, &Backtracking::new(None))
+            .unwrap();
+        assert_eq!(rescheduled.kept, vec!["b"]);
+        assert_eq!(rescheduled.moved.len(), 1);
+        assert_eq!(rescheduled.moved[0].id, "a");
+        assert_eq!(rescheduled.dropped, vec!["c"]);
+        assert_eq!(rescheduled.added, vec!["d"]);
+
+        // Nudging both meetings along by a slot beats moving one of them far away
+        let rescheduled = changed
+            .reschedule(
+                &previous,
+                Disruption::LeastMovement,
+                &Backtracking::new(None),
+            )
+            .unwrap();
+        assert!(rescheduled.kept.is_empty());
+        assert_eq!(
+            rescheduled
+                .moved
+                .iter()
+                .map(|m| (m.id.as_str(), m.to))
+                .collect::<Vec<_>>(),
+            vec![("a", TimeRange::new(1, 2)), ("b", TimeRange::new(3, 4))]
+        );
+
+        // a can no longer take place at all and e never fits, which leaves them out
+        // rather than failing the whole schedule
+        let rescheduled = Schedule::new(
+            vec![
+                Meeting::new("a", vec![TimeRange::new(0, 7)], 2),
+                Meeting::new("b", TimeSet::new(), 2),
+                Meeting::new("e", TimeSet::new(), 9),
+            ],
+            vec![TimeRange::new(0, 7)],
+        )
+        .reschedule(
+            &previous,
+            Disruption::FewestMoves,
+            &Auto::new(Backtracking::new(None)),
+        )
+        .unwrap();
+        assert_eq!(rescheduled.kept, vec!["b"]);
+        assert_eq!(rescheduled.dropped, vec!["a", "c", "e"]);
+        assert_eq!(rescheduled.result.cost, 0);
+    }
+
+    #[test]
+    fn aligned_start_times() {
+        use crate::meeting::Meeting;
+        use crate::schedule::Schedule;
+        use crate::time::{TimeRange, TimeSet};
+
+        let meetings = vec![
+            Meeting::new("a", vec![TimeRange::new(0, 0)], 2),
+            Meeting::new("b", TimeSet::new(), 2),
+        ];
+        let schedule =
+            Schedule::new(meetings.clone(), vec![TimeRange::new(0, 7)]).with_alignment(2);
+        // Past its first window, a may also start at 4 or 6, and b at 2, 4 or 6
+        assert_eq!(schedule.compute_windows(), 2 * 3);
+
+        // Only meetings without their own alignment are held to the schedule's
+        let mut meetings = meetings;
+        meetings.push(
+            Meeting::new("c", vec![TimeRange::new(0, 0), TimeRange::new(2, 7)], 1)
+                .with_alignment(1),
+        );
+        let result = Schedule::new(meetings, vec![TimeRange::new(0, 7)])
+            .with_alignment(2)
+            .schedule_meetings(None, None, None)
+            .unwrap();
+        let start = |id: &str| {
+            result
+                .results
+                .iter()
+                .find(|m| m.id == id)
+                .unwrap()
+                .time
+                .start
+        };
+        assert_eq!(start("c"), 1);
+        assert_eq!(start("a") % 2, 0);
+        assert_eq!(start("b") % 2, 0);
+        assert!(start("b") >= 2);
+
+        // Counted from the start of each day, or of each boundary, when they are set
+        let late = vec![Meeting::new("late", vec![TimeRange::new(0, 4)], 2)];
+        for schedule in [
+            Schedule::new(late.clone(), vec![TimeRange::new(0, 9)]).with_slots_per_day(5),
+            Schedule::new(late, vec![TimeRange::new(0, 9)]).with_boundaries(vec![5]),
+        ] {
+            let result = schedule
+                .with_alignment(2)
+                .schedule_meetings(None, None, None)
+                .unwrap();
+            assert_eq!(result.results[0].time.start % 2, 1);
+        }
+    }
+
+    #[test]
+    fn boundaries() {
+        use crate::meeting::Meeting;
+        use crate::schedule::{Schedule, ValidationError};
+        use crate::time::{TimeRange, TimeSet};
+
+        let meeting = |id: &str| Meeting::new(id, TimeSet::new(), 3_u8);
+
+        // Three meetings fit back to back, but not once the day is cut into pieces
+        let schedule = |meetings| {
+            Schedule::new(meetings, vec![TimeRange::new(0, 9)]).with_boundaries(vec![2, 7])
+        };
+        assert!(Schedule::new(
+            vec![meeting("a"), meeting("b"), meeting("c")],
+            vec![TimeRange::new(0, 9)]
+        )
+        .schedule_meetings(None, None, None)
+        .is_ok());
+        assert_eq!(
+            schedule(vec![meeting("a"), meeting("b"), meeting("c")])
+                .setup()
+                .err(),
+            Some(ValidationError::PigeonholeError {
+                pigeons: 9,
+                pigeon_holes: 8
+            })
+        );
+
+        let result = schedule(vec![meeting("a"), meeting("b")])
+            .schedule_meetings(None, None, None)
+            .unwrap();
+        for meeting in result.results {
+            assert!(![2, 7]
+                .iter()
+                .any(|&b| meeting.time.start < b && b <= meeting.time.end));
+        }
+    }
+
+    #[test]
+    fn load_limits() {
+        use crate::meeting::{Meeting, MeetingParticipants};
+        use crate::participant::Participant;
+        use crate::schedule::{Schedule, ValidationError};
+        use crate::time::{TimeRange, TimeSet};
+
+        let meetings = |participant: &Participant<u8>, count: usize, duration: u8| {
+            (0..count)
+                .map(|n| {
+                    Meeting::from(MeetingParticipants::new(
+                        &n.to_string(),
+                        vec![participant.clone()],
+                        duration,
+                    ))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Two days of four slots, with at most two meetings a day and a break after each
+        let ann = Participant::new("ann", TimeSet::new())
+            .with_max_meetings_per_day(2)
+            .with_min_break(1);
+        let result = Schedule::new(meetings(&ann, 3, 1), vec![TimeRange::new(0, 7)])
+            .with_slots_per_day(4)
+            .schedule_meetings(None, None, None)
+            .unwrap();
+        let mut times = result.results.iter().map(|m| m.time).collect::<Vec<_>>();
+        times.sort_by_key(|t| t.start);
+        for pair in times.windows(2) {
+            assert!(pair[1].start > pair[0].end + 1);
+        }
+        for day in 0..2 {
+            assert!(times.iter().filter(|t| t.start / 4 == day).count() <= 2);
+        }
+
+        let cid = Participant::new("cid", TimeSet::new()).with_max_meetings_per_day(2);
+        assert_eq!(
+            Schedule::new(meetings(&cid, 5, 1), vec![TimeRange::new(0, 7)])
+                .with_slots_per_day(4)
+                .schedule_meetings(None, None, None)
+                .err(),
+            Some(ValidationError::LoadLimitExceeded {
+                participant: "cid".to_string(),
+                limit: "at most 2 meetings a day".to_string()
+            })
+        );
+        assert!(matches!(
+            Schedule::new(meetings(&ann, 1, 1), vec![TimeRange::new(0, 7)]).setup(),
+            Err(ValidationError::InvalidData { .. })
+        ));
+
+        // Three meetings only fit back to back, which is more than bob can take
+        let bob = Participant::new("bob", TimeSet::new()).with_max_consecutive(2);
+        assert!(
+            Schedule::new(meetings(&bob, 2, 1), vec![TimeRange::new(0, 2)])
+                .schedule_meetings(None, None, None)
+                .is_ok()
+        );
+        let exceeded = Some(ValidationError::LoadLimitExceeded {
+            participant: "bob".to_string(),
+            limit: "at most 2 slots of meetings in a row".to_string(),
+        });
+        assert_eq!(
+            Schedule::new(meetings(&bob, 3, 1), vec![TimeRange::new(0, 2)])
+                .schedule_meetings(None, None, None)
+                .err(),
+            exceeded
+        );
+        assert_eq!(
+            Schedule::new(meetings(&bob, 1, 3), vec![TimeRange::new(0, 9)])
+                .setup()
+                .err(),
+            exceeded
+        );
+
+        // Runs reaching the last slot there is
+        assert!(
+            Schedule::new(meetings(&bob, 2, 1), vec![TimeRange::new(254, 255)])
+                .schedule_meetings(None, None, None)
+                .is_ok()
+        );
+        assert_eq!(
+            Schedule::new(meetings(&bob, 3, 1), vec![TimeRange::new(253, 255)])
+                .schedule_meetings(None, None, None)
+                .err(),
+            exceeded
+        );
+    }
+
+    #[test]
+    fn solvers() {
+        use crate::meeting::Meeting;
+        use crate::schedule::{Schedule, ValidationError};
+        use crate::solver::{Auto, Backtracking};
+        use crate::time::TimeRange;
+
+        let schedule = Schedule::new(
+            vec![
+                Meeting::new("a", vec![TimeRange::new(0, 3)], 1_u8),
+                Meeting::new("b", vec![TimeRange::new(0, 3)], 1),
+                Meeting::new("c", vec![TimeRange::new(0, 3)], 1),
+                Meeting::new("d", vec![], 1),
+            ],
+            vec![TimeRange::new(0, 5)],
+        );
+
+        assert_eq!(
+            schedule.schedule_with(&Backtracking::new(Some(1))).err(),
+            Some(ValidationError::NoSolutionWithinIteration(1))
+        );
+        assert_eq!(
+            schedule.schedule_with(&Auto::default()).err(),
+            schedule.schedule_meetings(None, None, None).err()
+        );
+
+        let schedule = Schedule::new(
+            vec![
+                Meeting::new("a", vec![], 2_u8),
+                Meeting::new("b", vec![], 2),
+            ],
+            vec![TimeRange::new(0, 5)],
+        );
+        let times = |result: crate::schedule::ScheduleResult<u8>| {
+            result
+                .results
+                .into_iter()
+                .map(|m| m.time)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            times(schedule.schedule_with(&Backtracking::default()).unwrap()),
+            times(schedule.schedule_meetings(None, None, None).unwrap())
+        );
+    }
+
+    #[test]
+    fn matching() {
+        use crate::meeting::{Meeting, MeetingParticipants};
+        use crate::participant::Participant;
+        use crate::schedule::{Schedule, ValidationError};
+        use crate::solver::Matching;
+        use crate::time::TimeRange;
+
+        // Backtracking has to try every placement of the other five to prove this
+        let schedule: Schedule<u16> = Schedule::new(
+            vec![
+                Meeting::new("1", vec![TimeRange::new(1, 1000)], 1),
+                Meeting::new("2", vec![TimeRange::new(1, 1000)], 1),
+                Meeting::new("3", vec![], 1),
+                Meeting::new("4", vec![], 1),
+                Meeting::new("5", vec![], 1),
+                Meeting::new("6", vec![], 1),
+                Meeting::new("7", vec![], 1),
+            ],
+            vec![TimeRange::new(0, 1000)],
+        );
+        assert_eq!(
+            schedule.schedule_meetings(None, None, None).err(),
+            Some(ValidationError::NoSolution)
+        );
+        assert_eq!(
+            schedule.schedule_with(&Matching).err(),
+            Some(ValidationError::HallViolation {
+                meetings: vec!["1".to_string(), "2".to_string()],
+                slots: 1
+            })
+        );
+
+        // Every meeting only fits after the one before, in a long chain
+        let schedule: Schedule<u16> = Schedule::new(
+            (0..300)
+                .map(|n| Meeting::new(&n.to_string(), vec![TimeRange::new(0, n)], 1))
+                .collect(),
+            vec![TimeRange::new(0, 300)],
+        );
+        let result = schedule.schedule_with(&Matching).unwrap();
+        assert_eq!(result.results.len(), 300);
+        for (slot, meeting) in result.results.iter().enumerate() {
+            assert_eq!(
+                meeting.time,
+                TimeRange::new(slot as u16 + 1, slot as u16 + 1)
+            );
+        }
+
+        // Meetings of different people are matched separately, and may share slots
+        let meeting = |id: &str, person: &str| -> Meeting<u8> {
+            MeetingParticipants::new(id, vec![Participant::new(person, vec![])], 1).into()
+        };
+        let schedule = Schedule::new(
+            vec![
+                meeting("a1", "ann"),
+                meeting("a2", "ann"),
+                meeting("b1", "bob"),
+                meeting("b2", "bob"),
+            ],
+            vec![TimeRange::new(0, 1)],
+        );
+        let setup = schedule.setup().unwrap();
+        assert!(Matching::suits(&schedule, &setup));
+        let result = schedule.schedule_meetings(None, None, None).unwrap();
+        assert_eq!(result.results.len(), 4);
+
+        // A meeting of both does not split into groups, so this is left to the search
+        let schedule = Schedule::new(
+            vec![
+                meeting("a1", "ann"),
+                meeting("b1", "bob"),
+                MeetingParticipants::new(
+                    "both",
+                    vec![
+                        Participant::new("ann", vec![]),
+                        Participant::new("bob", vec![]),
+                    ],
+                    1,
+                )
+                .into(),
+            ],
+            vec![TimeRange::new(0, 1)],
+        );
+        assert!(!Matching::suits(&schedule, &schedule.setup().unwrap()));
+        assert!(schedule.schedule_meetings(None, None, None).is_ok());
+    }
+
+    #[test]
+    fn partial_schedules() {
+        use crate::meeting::Meeting;
+        use crate::schedule::Schedule;
+        use crate::time::TimeRange;
+
+        // Six meetings, but only five slots any of them can take
+        let schedule: Schedule<u8> = Schedule::new(
+            vec![
+                Meeting::new("1", vec![TimeRange::new(2, 5)], 1),
+                Meeting::new("2", vec![TimeRange::new(0, 0), TimeRange::new(2, 5)], 1),
+                Meeting::new("3", vec![TimeRange::new(0, 1), TimeRange::new(3, 5)], 1),
+                Meeting::new("4", vec![TimeRange::new(0, 2), TimeRange::new(4, 5)], 1),
+                Meeting::new("5", vec![TimeRange::new(0, 3), TimeRange::new(5, 5)], 1),
+                Meeting::new("6", vec![TimeRange::new(0, 3), TimeRange::new(5, 5)], 1),
+                // Can never take place, so is left out instead of failing the schedule
+                Meeting::new("late", vec![], 1).with_earliest_start(6),
+            ],
+            vec![TimeRange::new(0, 5)],
+        );
+        assert!(schedule.schedule_meetings(None, None, None).is_err());
+        let partial = schedule.schedule_partial(None, None, None).unwrap();
+        assert_eq!(partial.result.results.len(), 5);
+        assert_eq!(partial.priority, 5);
+        assert_eq!(partial.dropped.len(), 2);
+        assert!(partial.dropped.contains(&"late".to_string()));
+        assert_eq!(partial.result.cost, 0);
+
+        // A meeting worth more than the two it displaces is kept
+        let schedule: Schedule<u8> = Schedule::new(
+            vec![
+                Meeting::new("a", vec![], 2),
+                Meeting::new("b", vec![], 2),
+                Meeting::new("all-hands", vec![], 4).with_priority(3),
+            ],
+            vec![TimeRange::new(0, 3)],
+        );
+        let partial = schedule.schedule_partial(None, None, None).unwrap();
+        assert_eq!(partial.dropped, vec!["a", "b"]);
+        assert_eq!(partial.priority, 3);
+
+        // Nothing is left out when everything fits
+        let schedule: Schedule<u8> = Schedule::new(
+            vec![Meeting::new("a", vec![], 2), Meeting::new("b", vec![], 2)],
+            vec![TimeRange::new(0, 3)],
+        );
+        let partial = schedule.schedule_partial(None, None, None).unwrap();
+        assert!(partial.dropped.is_empty());
+        assert_eq!(partial.priority, 2);
+    }
+
+    #[test]
+    fn diagnosis() {
+        use crate::constraint::Constraint;
+        use crate::meeting::{Meeting, MeetingParticipants};
+        use crate::participant::Participant;
+        use crate::schedule::Schedule;
+        use crate::time::TimeRange;
+
+        let meeting = |id: &str, person: &str| -> Meeting<u8> {
+            MeetingParticipants::new(id, vec![Participant::new(person, vec![])], 2).into()
+        };
+        let meetings = || {
+            vec![
+                meeting("a1", "ann"),
+                meeting("b1", "bob"),
+                meeting("a2", "ann"),
+                meeting("a3", "ann"),
+                meeting("b2", "bob"),
+            ]
+        };
+
+        // Ann needs six slots out of five, while bob fits
+        let diagnosis = Schedule::new(meetings(), vec![TimeRange::new(0, 4)])
+            .diagnose(None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(diagnosis.meetings, vec!["a1", "a2", "a3"]);
+        assert_eq!(diagnosis.window, vec![TimeRange::new(0, 4)].into());
+        assert_eq!((diagnosis.needed, diagnosis.available), (Some(6), Some(5)));
+
+        assert!(Schedule::new(meetings(), vec![TimeRange::new(0, 5)])
+            .diagnose(None)
+            .unwrap()
+            .is_none());
+
+        // Plenty of room, but the constraints go round in a circle
+        let diagnosis = Schedule::new(meetings(), vec![TimeRange::new(0, 9)])
+            .with_constraints(vec![
+                Constraint::Before {
+                    first: "b1".to_string(),
+                    second: "b2".to_string(),
+                },
+                Constraint::Before {
+                    first: "a1".to_string(),
+                    second: "b1".to_string(),
+                },
+                Constraint::Before {
+                    first: "b2".to_string(),
+                    second: "b1".to_string(),
+                },
+            ])
+            .diagnose(None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(diagnosis.meetings, vec!["b1", "b2"]);
+        assert_eq!((diagnosis.needed, diagnosis.available), (None, None));
+    }
+
+    #[test]
+    fn suggestions() {
+        use crate::meeting::{Meeting, MeetingParticipants};
+        use crate::participant::Participant;
+        use crate::schedule::{Relaxation, Schedule, Suggestion};
+        use crate::time::TimeRange;
+
+        let ann = Participant::new("ann", vec![TimeRange::new(0, 1)]);
+        let meetings = || -> Vec<Meeting<u8>> {
+            vec![
+                MeetingParticipants::new("1:1", vec![ann.clone()], 1).into(),
+                MeetingParticipants::new("sync", vec![ann.clone()], 1).into(),
+            ]
+        };
+
+        // Ann is only free for one of her meetings
+        let schedule = Schedule::new(meetings(), vec![TimeRange::new(0, 2)]);
+        let suggestions = schedule.suggest(None).unwrap();
+        assert_eq!(
+            suggestions[0],
+            Suggestion {
+                relaxation: Relaxation::Unblock {
+                    meeting: "1:1".to_string(),
+                    participants: vec!["ann".to_string()],
+                    time: TimeRange::new(0, 0).into(),
+                },
+                cost: 1,
+            }
+        );
+        assert!(suggestions.contains(&Suggestion {
+            relaxation: Relaxation::Extend {
+                time: TimeRange::new(3, 3)
+            },
+            cost: 1,
+        }));
+        assert!(
+            matches!(
+                suggestions.last().map(|s| &s.relaxation),
+                Some(Relaxation::Drop { .. })
+            ),
+            "dropping a meeting comes last"
+        );
+        assert_eq!(suggestions.last().map(|s| s.cost), Some(1));
+        assert!(!suggestions
+            .iter()
+            .any(|s| matches!(s.relaxation, Relaxation::Shorten { .. })));
+
+        // Every suggestion makes the schedule work
+        for suggestion in suggestions {
+            let mut schedule = Schedule::new(meetings(), vec![TimeRange::new(0, 2)]);
+            match suggestion.relaxation {
+                Relaxation::Unblock { meeting, time, .. } => {
+                    let meeting = schedule
+                        .meetings
+                        .iter_mut()
+                        .find(|m| m.id == meeting)
+                        .unwrap();
+                    meeting.blocked_times = meeting.blocked_times.difference(&time);
+                }
+                Relaxation::Extend { time } => {
+                    schedule.availability = schedule.availability.union(&time.into())
+                }
+                Relaxation::Drop { meeting } => schedule.meetings.retain(|m| m.id != meeting),
+                Relaxation::Shorten { .. } => unreachable!(),
+            }
+            assert!(schedule.schedule_meetings(None, None, None).is_ok());
+        }
+
+        assert!(Schedule::new(meetings(), vec![TimeRange::new(0, 3)])
+            .suggest(None)
+            .unwrap()
+            .is_empty());
+
+        // The sync is pinned to the only free window, which leaves the review to make
+        // room in time only bob has blocked once it may start
+        let bob = Participant::new("bob", vec![TimeRange::new(2, 3)]);
+        let review: Meeting<u8> = MeetingParticipants::new(
+            "review",
+            vec![
+                Participant::new("ann", vec![TimeRange::new(0, 1)]),
+                bob.clone(),
+            ],
+            2,
+        )
+        .into();
+        let sync: Meeting<u8> = MeetingParticipants::new("sync", vec![bob], 2).into();
+        let suggestions = Schedule::new(
+            vec![
+                review.with_earliest_start(2),
+                sync.with_fixed_time(TimeRange::new(4, 5)),
+            ],
+            vec![TimeRange::new(0, 5)],
+        )
+        .suggest(None)
+        .unwrap();
+        assert!(suggestions.contains(&Suggestion {
+            relaxation: Relaxation::Unblock {
+                meeting: "review".to_string(),
+                participants: vec!["bob".to_string()],
+                time: TimeRange::new(2, 3).into(),
+            },
+            cost: 2,
+        }));
+        assert!(!suggestions.iter().any(|s| matches!(
+            &s.relaxation,
+            Relaxation::Unblock { participants, .. } if participants.contains(&"ann".to_string())
+        )));
+
+        // The sync takes the only free window, and every other window of the review
+        // holds time blocked by both of them
+        let review: Meeting<u8> = MeetingParticipants::new(
+            "review",
+            vec![
+                Participant::new("ann", vec![TimeRange::new(1, 1)]),
+                Participant::new("bob", vec![TimeRange::new(3, 3)]),
+            ],
+            4,
+        )
+        .into();
+        let sync: Meeting<u8> =
+            MeetingParticipants::new("sync", vec![Participant::new("bob", vec![])], 4).into();
+        let suggestions = Schedule::new(
+            vec![review, sync.with_fixed_time(TimeRange::new(10, 13))],
+            vec![TimeRange::new(0, 4), TimeRange::new(10, 13)],
+        )
+        .suggest(None)
+        .unwrap();
+        assert!(suggestions.contains(&Suggestion {
+            relaxation: Relaxation::Unblock {
+                meeting: "review".to_string(),
+                participants: vec!["ann".to_string(), "bob".to_string()],
+                time: vec![TimeRange::new(1, 1), TimeRange::new(3, 3)].into(),
+            },
+            cost: 2,
+        }));
+    }
 }
@@ -1,8 +1,11 @@
-use crate::participant::Participant;
-use crate::time::{Available, Blocks, TimeRange, Validate};
+use crate::participant::{LoadLimits, Participant, SlotPreference};
+use crate::resource::ResourceRequest;
+use crate::time::{Available, TimeRange, TimeSet, Validate};
 use log::debug;
 use num::{CheckedAdd, CheckedSub, Integer, One};
+use std::collections::BTreeMap;
 use std::fmt::{Debug, Display};
+use std::ops::RangeInclusive;
 
 #[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 #[derive(Clone, Debug)]
@@ -13,6 +16,13 @@ where
     pub id: String,
     pub participants: Vec<Participant<N>>,
     pub duration: N,
+    /// Attendees who are welcome, but not needed
+    #[cfg_attr(feature = "serde", serde(rename = "optionalParticipants", default))]
+    pub optional_participants: Vec<Participant<N>>,
+    /// When set, the meeting only needs this many of its participants and optional
+    /// participants, instead of every participant
+    #[cfg_attr(feature = "serde", serde(rename = "minAttendance", default))]
+    pub min_attendance: Option<usize>,
 }
 
 impl<N> MeetingParticipants<N>
@@ -24,8 +34,25 @@ where
             id: id.to_string(),
             participants,
             duration,
+            optional_participants: vec![],
+            min_attendance: None,
         }
     }
+
+    /// Only needs `min_attendance` of the participants to be free, such as for a quorum
+    pub fn with_min_attendance(mut self, min_attendance: usize) -> MeetingParticipants<N> {
+        self.min_attendance = Some(min_attendance);
+        self
+    }
+
+    /// Adds attendees who are welcome, but not needed. Times they are free are preferred.
+    pub fn with_optional_participants(
+        mut self,
+        optional_participants: Vec<Participant<N>>,
+    ) -> MeetingParticipants<N> {
+        self.optional_participants = optional_participants;
+        self
+    }
 }
 
 impl<N> From<MeetingParticipants<N>> for Meeting<N>
@@ -33,21 +60,108 @@ where
     N: Integer + One + Copy + Display + Debug + CheckedAdd,
 {
     fn from(meeting: MeetingParticipants<N>) -> Self {
-        use crate::time::TimeMerge;
+        if let Some(min_attendance) = meeting.min_attendance {
+            // Nobody is needed in particular, so everyone is optional. Their limits and
+            // preferences still carry over, as they would for a meeting without a quorum.
+            let limits = meeting
+                .participants
+                .iter()
+                .filter(|p| p.limits.is_limited())
+                .map(|p| (p.id.clone(), p.limits))
+                .collect();
+            let (preferences, participants): (Vec<_>, Vec<_>) = meeting
+                .participants
+                .into_iter()
+                .map(|mut p| (std::mem::take(&mut p.preferences), p))
+                .unzip();
+            return Meeting::new(&meeting.id, TimeSet::new(), meeting.duration)
+                .with_limits(limits)
+                .with_preferences(preferences.into_iter().flatten().collect())
+                .with_optional_participants(
+                    participants
+                        .into_iter()
+                        .chain(meeting.optional_participants)
+                        .collect(),
+                )
+                .with_min_attendance(min_attendance);
+        }
+
         Meeting::new(
             &meeting.id,
             meeting
                 .participants
                 .iter()
                 .flat_map(|p| p.blocked_times.iter())
-                .time_merge()
-                .collect(),
+                .collect::<TimeSet<N>>(),
             meeting.duration,
         )
+        .with_participants(meeting.participants.iter().map(|p| p.id.clone()).collect())
+        .with_participant_blocked_times(
+            meeting
+                .participants
+                .iter()
+                .filter(|p| !p.blocked_times.is_empty())
+                .map(|p| (p.id.clone(), p.blocked_times.clone()))
+                .collect(),
+        )
+        .with_limits(
+            meeting
+                .participants
+                .iter()
+                .filter(|p| p.limits.is_limited())
+                .map(|p| (p.id.clone(), p.limits))
+                .collect(),
+        )
+        .with_preferences(
+            meeting
+                .participants
+                .into_iter()
+                .flat_map(|p| p.preferences)
+                .collect(),
+        )
+        .with_optional_participants(meeting.optional_participants)
+    }
+}
+
+impl<N> Available<N> for MeetingParticipants<N>
+where
+    N: Integer + One + Copy + Display + Debug + CheckedAdd + CheckedSub,
+{
+    /// When every participant is needed, the meeting fits wherever all of them are free.
+    /// With a `min_attendance`, it fits wherever enough of them are free.
+    /// ```
+    /// use zeitplan_libs::meeting::MeetingParticipants;
+    /// use zeitplan_libs::participant::Participant;
+    /// use zeitplan_libs::time::{Available, TimeRange, TimeSet};
+    ///
+    /// let meeting = MeetingParticipants::new(
+    ///     "standup",
+    ///     vec![
+    ///         Participant::new("ann", vec![TimeRange::new(0, 3)]),
+    ///         Participant::new("bob", vec![TimeRange::new(2, 5)]),
+    ///         Participant::new("cid", vec![TimeRange::new(4, 7)]),
+    ///     ],
+    ///     2,
+    /// )
+    /// .with_min_attendance(2);
+    ///
+    /// let available_time: TimeSet<u8> = TimeRange::new(0, 9).into();
+    ///
+    /// assert_eq!(
+    ///     meeting.get_availability(&available_time).as_slice(),
+    ///     &[TimeRange::new(0, 1), TimeRange::new(6, 9)]
+    /// );
+    /// ```
+    fn get_availability(&self, available_times: &TimeSet<N>) -> TimeSet<N> {
+        Meeting::from(self.clone()).get_availability(available_times)
     }
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "N: serde::Deserialize<'de>"))
+)]
 #[derive(Clone, Debug)]
 pub struct Meeting<N>
 where
@@ -55,8 +169,69 @@ where
 {
     pub id: String,
     #[cfg_attr(feature = "serde", serde(rename = "blockedTimes"))]
-    pub blocked_times: Vec<TimeRange<N>>,
+    pub blocked_times: TimeSet<N>,
+    /// The blocked times of each attendee, by id, which together make up `blocked_times`
+    #[cfg_attr(feature = "serde", serde(rename = "participantBlockedTimes", default))]
+    pub participant_blocked_times: BTreeMap<String, TimeSet<N>>,
+    /// How long the meeting is, or the shortest it may be when it has a `max_duration`
     pub duration: N,
+    /// When set, the meeting may run this long, and longer placements are preferred
+    #[cfg_attr(feature = "serde", serde(rename = "maxDuration", default))]
+    pub max_duration: Option<N>,
+    /// Ids of the attendees. Meetings sharing an attendee can not overlap, and a
+    /// meeting without any is assumed to share one with every other meeting.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub participants: Vec<String>,
+    /// Resources this meeting books, one for every request
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub resources: Vec<ResourceRequest>,
+    /// Attendees who are welcome, but not needed. Their blocked times are avoided where possible.
+    #[cfg_attr(feature = "serde", serde(rename = "optionalParticipants", default))]
+    pub optional_participants: Vec<Participant<N>>,
+    /// How many of the `optional_participants` must attend
+    #[cfg_attr(feature = "serde", serde(rename = "minAttendance", default))]
+    pub min_attendance: Option<usize>,
+    /// The preferences of the attendees, which make some times better than others
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub preferences: Vec<SlotPreference<N>>,
+    /// The load limits of the attendees, by id
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub limits: BTreeMap<String, LoadLimits<N>>,
+    /// The meeting may not start before this time
+    #[cfg_attr(feature = "serde", serde(rename = "earliestStart", default))]
+    pub earliest_start: Option<N>,
+    /// The meeting must be over by this time
+    #[cfg_attr(feature = "serde", serde(rename = "latestEnd", default))]
+    pub latest_end: Option<N>,
+    /// Links this meeting to the other occurrences of its series
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub series: Option<SeriesLink<N>>,
+    /// An already confirmed time, which the other meetings are placed around
+    #[cfg_attr(feature = "serde", serde(rename = "fixedTime", default))]
+    pub fixed_time: Option<TimeRange<N>>,
+    /// Only start on multiples of this many slots, counted as by `Schedule::with_alignment`.
+    /// Falls back to the schedule's alignment when unset.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub alignment: Option<N>,
+    /// How much placing this meeting matters when not every meeting fits. Defaults to 1.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub priority: Option<u64>,
+}
+
+/// How one occurrence of a `MeetingSeries` relates to the others
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SeriesLink<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    /// The id shared by every occurrence of the series
+    pub id: String,
+    /// How many slots after the first occurrence this one takes place
+    pub shift: N,
+    /// How far each occurrence may move away from the time shared by the series
+    #[cfg_attr(feature = "serde", serde(rename = "maxDeviation"))]
+    pub max_deviation: N,
 }
 
 impl<N> Validate for Meeting<N>
@@ -70,6 +245,14 @@ where
                 "Meeting {} has an invalid duration {}",
                 self.id, self.duration
             ))
+        } else if self.max_duration.unwrap_or(self.duration) < self.duration {
+            debug!(target:"Meeting", "Invalid Meeting Found: {}", self.id);
+            Err(format!(
+                "Meeting {} may be at most {} long, which is shorter than its duration {}",
+                self.id,
+                self.max_duration.unwrap_or(self.duration),
+                self.duration
+            ))
         } else if let Some(Err(p)) = self
             .blocked_times
             .iter()
@@ -81,6 +264,46 @@ where
                 "Meeting {} has an invalid participant value:\n\t {}",
                 self.id, p
             ))
+        } else if self.alignment == Some(<N>::zero()) {
+            debug!(target:"Meeting", "Invalid Meeting Found: {}", self.id);
+            Err(format!("Meeting {} has an alignment of zero", self.id))
+        } else if let Some(Err(t)) = self.fixed_time.map(|t| t.validate()) {
+            debug!(target:"Meeting", "Invalid Meeting Found: {}", self.id);
+            Err(format!(
+                "Meeting {} has an invalid fixed time:\n\t {}",
+                self.id, t
+            ))
+        } else if let Some(Err(p)) = self
+            .optional_participants
+            .iter()
+            .map(|p| p.validate())
+            .find(Result::is_err)
+        {
+            debug!(target:"Meeting", "Invalid Meeting Found: {}", self.id);
+            Err(format!(
+                "Meeting {} has an invalid optional participant:\n\t {}",
+                self.id, p
+            ))
+        } else if let Some(Err(t)) = self
+            .preferences
+            .iter()
+            .flat_map(|p| p.times.iter())
+            .map(|t| t.validate())
+            .find(Result::is_err)
+        {
+            debug!(target:"Meeting", "Invalid Meeting Found: {}", self.id);
+            Err(format!(
+                "Meeting {} has an invalid preference:\n\t {}",
+                self.id, t
+            ))
+        } else if self.min_attendance.unwrap_or(0) > self.optional_participants.len() {
+            debug!(target:"Meeting", "Invalid Meeting Found: {}", self.id);
+            Err(format!(
+                "Meeting {} needs {} attendees, but only has {} optional participants",
+                self.id,
+                self.min_attendance.unwrap_or(0),
+                self.optional_participants.len()
+            ))
         } else {
             Ok(())
         }
@@ -93,7 +316,7 @@ where
     N: Integer + Copy + arbitrary::Arbitrary<'a> + Display + Debug,
 {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        let times = u.arbitrary::<Vec<TimeRange<N>>>()?;
+        let times = u.arbitrary::<TimeSet<N>>()?;
 
         let id = format!("{}", u.arbitrary::<uuid::Uuid>()?);
 
@@ -106,35 +329,205 @@ impl<N> Meeting<N>
 where
     N: Integer + One + Clone + Copy + Display + Debug,
 {
-    pub fn new(id: &str, blocked_times: Vec<TimeRange<N>>, duration: N) -> Meeting<N> {
+    pub fn new(id: &str, blocked_times: impl Into<TimeSet<N>>, duration: N) -> Meeting<N> {
         Meeting {
             id: id.to_string(),
-            blocked_times,
+            blocked_times: blocked_times.into(),
+            participant_blocked_times: BTreeMap::new(),
             duration,
+            max_duration: None,
+            participants: vec![],
+            resources: vec![],
+            optional_participants: vec![],
+            min_attendance: None,
+            preferences: vec![],
+            limits: BTreeMap::new(),
+            earliest_start: None,
+            latest_end: None,
+            series: None,
+            fixed_time: None,
+            alignment: None,
+            priority: None,
+        }
+    }
+
+    /// Sets how much placing this meeting matters when not every meeting fits
+    pub fn with_priority(mut self, priority: u64) -> Meeting<N> {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Only starts the meeting on multiples of `alignment` slots, such as on the hour
+    pub fn with_alignment(mut self, alignment: N) -> Meeting<N> {
+        self.alignment = Some(alignment);
+        self
+    }
+
+    /// Pins the meeting to `time`, such as when it has already been confirmed
+    pub fn with_fixed_time(mut self, time: TimeRange<N>) -> Meeting<N> {
+        self.fixed_time = Some(time);
+        self
+    }
+
+    /// Lets the meeting run anywhere from `min` to `max` slots, preferring longer placements.
+    /// Only `min` is needed for the meeting to fit.
+    pub fn with_duration_range(mut self, min: N, max: N) -> Meeting<N> {
+        self.duration = min;
+        self.max_duration = Some(max);
+        self
+    }
+
+    /// The shortest and longest the meeting may be
+    /// ```
+    /// use zeitplan_libs::meeting::Meeting;
+    /// use zeitplan_libs::time::TimeSet;
+    ///
+    /// let workshop = Meeting::new("workshop", TimeSet::new(), 2_u8);
+    /// assert_eq!(workshop.duration_range(), 2..=2);
+    /// assert_eq!(workshop.with_duration_range(2, 4).duration_range(), 2..=4);
+    /// ```
+    pub fn duration_range(&self) -> RangeInclusive<N> {
+        self.duration..=self.max_duration.unwrap_or(self.duration)
+    }
+
+    /// Keeps the meeting from starting before `earliest_start`, such as "not before Monday noon"
+    pub fn with_earliest_start(mut self, earliest_start: N) -> Meeting<N> {
+        self.earliest_start = Some(earliest_start);
+        self
+    }
+
+    /// Keeps the meeting from ending after `latest_end`, such as "before the Thursday release"
+    pub fn with_latest_end(mut self, latest_end: N) -> Meeting<N> {
+        self.latest_end = Some(latest_end);
+        self
+    }
+
+    /// The part of `times` between `earliest_start` and `latest_end`
+    /// ```
+    /// use zeitplan_libs::meeting::Meeting;
+    /// use zeitplan_libs::time::{TimeRange, TimeSet};
+    ///
+    /// let meeting = Meeting::new("release", TimeSet::new(), 1)
+    ///     .with_earliest_start(3)
+    ///     .with_latest_end(8);
+    /// let times: TimeSet<u8> = vec![TimeRange::new(0, 4), TimeRange::new(6, 10)].into();
+    ///
+    /// assert_eq!(
+    ///     meeting.within_bounds(&times).as_slice(),
+    ///     &[TimeRange::new(3, 4), TimeRange::new(6, 8)]
+    /// );
+    /// ```
+    pub fn within_bounds(&self, times: &TimeSet<N>) -> TimeSet<N> {
+        let bounds = match times.bounds() {
+            Some(bounds) if self.earliest_start.is_some() || self.latest_end.is_some() => bounds,
+            _ => return times.clone(),
+        };
+        let start = self
+            .earliest_start
+            .map_or(bounds.start, |s| s.max(bounds.start));
+        let end = self.latest_end.map_or(bounds.end, |e| e.min(bounds.end));
+        if start > end {
+            TimeSet::new()
+        } else {
+            times.intersection(&TimeRange::new(start, end).into())
         }
     }
 
+    /// Sets the ids of the attendees, so this meeting may run alongside meetings
+    /// without any of them
+    pub fn with_participants(mut self, participants: Vec<String>) -> Meeting<N> {
+        self.participants = participants;
+        self
+    }
+
+    /// Sets the attendees who are welcome, but not needed
+    pub fn with_optional_participants(
+        mut self,
+        optional_participants: Vec<Participant<N>>,
+    ) -> Meeting<N> {
+        self.optional_participants = optional_participants;
+        self
+    }
+
+    /// Only takes place when at least `min_attendance` of the optional participants can attend
+    pub fn with_min_attendance(mut self, min_attendance: usize) -> Meeting<N> {
+        self.min_attendance = Some(min_attendance);
+        self
+    }
+
+    /// Sets the preferences which make some times better for this meeting than others
+    pub fn with_preferences(mut self, preferences: Vec<SlotPreference<N>>) -> Meeting<N> {
+        self.preferences = preferences;
+        self
+    }
+
+    /// Sets the blocked times of each attendee, by id
+    pub fn with_participant_blocked_times(
+        mut self,
+        blocked_times: BTreeMap<String, TimeSet<N>>,
+    ) -> Meeting<N> {
+        self.participant_blocked_times = blocked_times;
+        self
+    }
+
+    /// Sets the load limits of the attendees, by id
+    pub fn with_limits(mut self, limits: BTreeMap<String, LoadLimits<N>>) -> Meeting<N> {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets the resources this meeting needs, such as a room
+    pub fn with_resources(mut self, resources: Vec<ResourceRequest>) -> Meeting<N> {
+        self.resources = resources;
+        self
+    }
+
     /// Meetings should be pre-sorted before attempting to schedule.
     /// This method produces a value for each meeting which represents
     /// the number of slots that this meeting can be scheduled within.
     ///
     /// We choose here to start with meetings with less overall availability
     /// to schedule _first_.
-    pub fn sort_val(&self, available_times: &[TimeRange<N>]) -> N {
+    pub fn sort_val(&self, available_times: &TimeSet<N>) -> N {
         available_times.iter().fold(<N>::one(), |acc, time| {
             acc + time.end - (self.duration + time.start + <N>::one())
         })
     }
+
+    /// Times at which fewer than `min_attendance` optional participants are free.
+    ///
+    /// This is checked one slot at a time, so the same attendees being free for
+    /// the whole meeting is left to the scheduler.
+    fn quorum_blocked(&self, min_attendance: usize) -> TimeSet<N> {
+        // A slot is blocked once more than `n - min_attendance` participants are busy
+        let busy = match (self.optional_participants.len() + 1).checked_sub(min_attendance) {
+            Some(busy) if busy > 0 => busy,
+            _ => return TimeSet::new(),
+        };
+        if busy > self.optional_participants.len() {
+            return TimeSet::new();
+        }
+
+        // covered[j] holds the slots where at least j + 1 participants are busy
+        let mut covered = vec![TimeSet::new(); busy];
+        for participant in &self.optional_participants {
+            for j in (1..busy).rev() {
+                let more = covered[j - 1].intersection(&participant.blocked_times);
+                covered[j] = covered[j].union(&more);
+            }
+            covered[0] = covered[0].union(&participant.blocked_times);
+        }
+        covered.pop().unwrap_or_default()
+    }
 }
 
 impl<N> Available<N> for Meeting<N>
 where
     N: Integer + One + Clone + Copy + CheckedAdd + CheckedSub + Display + Debug,
 {
-    /// Iterates each participant's `block_times`, sorts them,
-    /// then performs a `TimeMerge` to consolodate the combined block_times.
-    /// We then use this against the `available_times` to produce values for which
-    /// this meeting can be scheduled
+    /// Removes the combined `blocked_times` of every participant from the
+    /// `available_times` to produce values for which this meeting can be scheduled.
+    /// Times too short to hold the meeting's `duration` are dropped.
     ///
     /// # Examples
     /// ```
@@ -159,30 +552,132 @@ where
     /// let meeting = MeetingParticipants::new(&"1", participants, 1);
     /// let meeting: Meeting<u8> = meeting.into();
     ///
-    /// let available_time = vec![
-    ///     TimeRange::new(1, 4),
-    /// ];
+    /// let available_time = TimeRange::new(1, 4).into();
     ///
     /// assert_eq!(
-    ///     meeting.get_availability(&available_time),
-    ///     vec![TimeRange::new(2, 2)]
+    ///     meeting.get_availability(&available_time).as_slice(),
+    ///     &[TimeRange::new(2, 2)]
     /// );
     /// ```
-    fn get_availability(&self, available_times: &[TimeRange<N>]) -> Vec<TimeRange<N>> {
+    fn get_availability(&self, available_times: &TimeSet<N>) -> TimeSet<N> {
         if available_times.is_empty() {
-            return vec![];
+            return TimeSet::new();
         }
 
-        available_times
-            .iter()
-            .blocks(self.blocked_times.iter())
-            .filter(|&time| {
-                (time.end - time.start)
-                    .checked_add(&<N>::one())
-                    .map(|n| n >= self.duration)
-                    // Only happens if timespan is the max value available
-                    // - there would be no duration above this
-                    .unwrap_or(true)
+        let mut availability = self
+            .within_bounds(available_times)
+            .difference(&self.blocked_times);
+        if let Some(min_attendance) = self.min_attendance {
+            availability = availability.difference(&self.quorum_blocked(min_attendance));
+        }
+        availability.retain(|&time| {
+            (time.end - time.start)
+                .checked_add(&<N>::one())
+                .map(|n| n >= self.duration)
+                // Only happens if timespan is the max value available
+                // - there would be no duration above this
+                .unwrap_or(true)
+        });
+        availability
+    }
+}
+
+/// A meeting which takes place every `period` slots, such as a daily standup or a weekly 1:1.
+///
+/// Every occurrence lands at the same time of day (or week), give or take `max_deviation` slots.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct MeetingSeries<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    /// The first occurrence. Its release time and deadline move along with each occurrence.
+    pub meeting: Meeting<N>,
+    /// Slots from one occurrence to the next
+    pub period: N,
+    pub occurrences: usize,
+    #[cfg_attr(feature = "serde", serde(rename = "maxDeviation"))]
+    pub max_deviation: N,
+}
+
+impl<N> MeetingSeries<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    pub fn new(meeting: Meeting<N>, period: N, occurrences: usize) -> MeetingSeries<N> {
+        MeetingSeries {
+            meeting,
+            period,
+            occurrences,
+            max_deviation: <N>::zero(),
+        }
+    }
+
+    /// Allows each occurrence to move up to `max_deviation` slots away from the others
+    pub fn with_max_deviation(mut self, max_deviation: N) -> MeetingSeries<N> {
+        self.max_deviation = max_deviation;
+        self
+    }
+
+    /// Expands the series into one linked meeting per occurrence, numbered from 1.
+    /// Release times, deadlines and a fixed time all move along with each occurrence;
+    /// fails when any of them would move past the largest `N`.
+    /// ```
+    /// use zeitplan_libs::meeting::{Meeting, MeetingSeries};
+    /// use zeitplan_libs::time::{TimeRange, TimeSet};
+    ///
+    /// let standup = MeetingSeries::new(Meeting::new("standup", TimeSet::new(), 1), 24_u16, 5);
+    /// let meetings = standup.meetings().unwrap();
+    ///
+    /// assert_eq!(meetings.len(), 5);
+    /// assert_eq!(meetings[2].id, "standup#3");
+    /// assert_eq!(meetings[2].series.as_ref().unwrap().shift, 48);
+    ///
+    /// let pinned = Meeting::new("standup", TimeSet::new(), 1).with_fixed_time(TimeRange::new(9, 9));
+    /// let meetings = MeetingSeries::new(pinned.clone(), 24_u16, 2).meetings().unwrap();
+    /// assert_eq!(meetings[1].fixed_time, Some(TimeRange::new(33, 33)));
+    ///
+    /// assert!(MeetingSeries::new(pinned, 24_u16, 3000).meetings().is_err());
+    /// ```
+    pub fn meetings(&self) -> Result<Vec<Meeting<N>>, String>
+    where
+        N: CheckedAdd,
+    {
+        let overflow = |occurrence: usize| {
+            format!(
+                "Meeting series {} runs out of time at occurrence {}",
+                self.meeting.id, occurrence
+            )
+        };
+        let mut shift = <N>::zero();
+        (1..=self.occurrences)
+            .map(|occurrence| {
+                let moved = |time: N| time.checked_add(&shift).ok_or_else(|| overflow(occurrence));
+                let fixed_time = self
+                    .meeting
+                    .fixed_time
+                    .map(|time| {
+                        Ok::<_, String>(TimeRange::new(moved(time.start)?, moved(time.end)?))
+                    })
+                    .transpose()?;
+                let meeting = Meeting {
+                    id: format!("{}#{}", self.meeting.id, occurrence),
+                    earliest_start: self.meeting.earliest_start.map(moved).transpose()?,
+                    latest_end: self.meeting.latest_end.map(moved).transpose()?,
+                    fixed_time,
+                    series: Some(SeriesLink {
+                        id: self.meeting.id.clone(),
+                        shift,
+                        max_deviation: self.max_deviation,
+                    }),
+                    ..self.meeting.clone()
+                };
+                if occurrence < self.occurrences {
+                    shift = shift
+                        .checked_add(&self.period)
+                        .ok_or_else(|| overflow(occurrence + 1))?;
+                }
+                Ok(meeting)
             })
             .collect()
     }
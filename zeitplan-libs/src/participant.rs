@@ -1,9 +1,13 @@
-use crate::time::{Available, Blocks, TimeRange, Validate};
+use crate::time::{Available, TimeRange, TimeSet, Validate};
 use log::debug;
-use num::{CheckedAdd, CheckedSub, Integer, One};
+use num::{traits::AsPrimitive, CheckedAdd, CheckedSub, Integer, One};
 use std::fmt::{Debug, Display};
 
-#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "N: serde::Deserialize<'de>"))
+)]
 #[derive(Clone, Debug)]
 pub struct Participant<N>
 where
@@ -11,7 +15,154 @@ where
 {
     pub id: String,
     #[cfg_attr(feature = "serde", serde(rename = "blockedTimes"))]
-    pub blocked_times: Vec<TimeRange<N>>,
+    pub blocked_times: TimeSet<N>,
+    /// Times this participant would rather, or would rather not, meet
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub preferences: Vec<SlotPreference<N>>,
+    /// How much meeting this participant can take on
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub limits: LoadLimits<N>,
+}
+
+/// Limits on how many meetings a participant attends, and how closely together.
+/// Only meetings the participant is required at count towards these.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LoadLimits<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    /// At most this many meetings start on any one day
+    pub max_meetings_per_day: Option<usize>,
+    /// At most this many slots of meetings back to back
+    pub max_consecutive: Option<N>,
+    /// At least this many free slots between any two meetings
+    pub min_break: Option<N>,
+}
+
+impl<N> Default for LoadLimits<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    fn default() -> Self {
+        LoadLimits {
+            max_meetings_per_day: None,
+            max_consecutive: None,
+            min_break: None,
+        }
+    }
+}
+
+impl<N> LoadLimits<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    /// Whether any limit is set
+    pub fn is_limited(&self) -> bool {
+        self.max_meetings_per_day.is_some()
+            || self.max_consecutive.is_some()
+            || self.min_break.is_some()
+    }
+
+    /// The strictest of both sets of limits
+    pub fn strictest(&self, other: &LoadLimits<N>) -> LoadLimits<N> {
+        fn pick<T: Ord + Copy>(a: Option<T>, b: Option<T>, f: fn(T, T) -> T) -> Option<T> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(f(a, b)),
+                (a, b) => a.or(b),
+            }
+        }
+        LoadLimits {
+            max_meetings_per_day: pick(
+                self.max_meetings_per_day,
+                other.max_meetings_per_day,
+                std::cmp::min,
+            ),
+            max_consecutive: pick(self.max_consecutive, other.max_consecutive, std::cmp::min),
+            min_break: pick(self.min_break, other.min_break, std::cmp::max),
+        }
+    }
+}
+
+/// A single load limit, as reported when it cannot be kept
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LoadLimit<N> {
+    MeetingsPerDay(usize),
+    Consecutive(N),
+    Break(N),
+}
+
+impl<N> Display for LoadLimit<N>
+where
+    N: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadLimit::MeetingsPerDay(n) => write!(f, "at most {} meetings a day", n),
+            LoadLimit::Consecutive(n) => write!(f, "at most {} slots of meetings in a row", n),
+            LoadLimit::Break(n) => write!(f, "at least {} free slots between meetings", n),
+        }
+    }
+}
+
+/// How a participant feels about meeting at some time
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Preference {
+    Preferred,
+    Neutral,
+    /// Possible, but only if nothing better can be found
+    Avoid,
+}
+
+/// A preference for a set of times, and how much it matters
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Debug)]
+pub struct SlotPreference<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    pub times: TimeSet<N>,
+    pub preference: Preference,
+    pub weight: u64,
+}
+
+impl<N> SlotPreference<N>
+where
+    N: Integer + One + Copy + Display + Debug + CheckedAdd + AsPrimitive<usize>,
+{
+    /// The cost of meeting at `time`: `weight` for every slot outside of preferred
+    /// times, or inside of avoided times
+    /// ```
+    /// use zeitplan_libs::participant::{Preference, SlotPreference};
+    /// use zeitplan_libs::time::TimeRange;
+    ///
+    /// let mornings = SlotPreference {
+    ///     times: vec![TimeRange::new(0, 3), TimeRange::new(10, 13)].into(),
+    ///     preference: Preference::Preferred,
+    ///     weight: 2,
+    /// };
+    ///
+    /// assert_eq!(mornings.cost(&TimeRange::new(2, 3)), 0);
+    /// assert_eq!(mornings.cost(&TimeRange::new(3, 5)), 4);
+    /// ```
+    pub fn cost(&self, time: &TimeRange<N>) -> u64 {
+        let slots = |n: N| n.as_() as u64;
+        let inside = self
+            .times
+            .intersection(&(*time).into())
+            .len_in_slots()
+            .map(slots)
+            .unwrap_or(0);
+        let affected = match self.preference {
+            Preference::Preferred => slots(time.end - time.start + <N>::one()) - inside,
+            Preference::Neutral => 0,
+            Preference::Avoid => inside,
+        };
+        affected.saturating_mul(self.weight)
+    }
 }
 
 #[cfg(feature = "arbitrary")]
@@ -20,7 +171,7 @@ where
     N: Integer + Copy + arbitrary::Arbitrary<'a> + Display + Debug,
 {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        let blocked_times = u.arbitrary::<Vec<TimeRange<N>>>()?;
+        let blocked_times = u.arbitrary::<TimeSet<N>>()?;
         let id = format!("{}", u.arbitrary::<uuid::Uuid>()?);
         Ok(Participant::new(&id, blocked_times))
     }
@@ -42,6 +193,26 @@ where
                 "Participant {} has invalid Blocked Times:\n\t{}",
                 self.id, b
             ))
+        } else if let Some(Err(t)) = self
+            .preferences
+            .iter()
+            .flat_map(|p| p.times.iter())
+            .map(|t| t.validate())
+            .find(Result::is_err)
+        {
+            debug!(target:"Participant", "Invalid Participant Found: {}", self.id);
+            Err(format!(
+                "Participant {} has an invalid preference:\n\t{}",
+                self.id, t
+            ))
+        } else if self.limits.max_meetings_per_day == Some(0)
+            || self.limits.max_consecutive == Some(<N>::zero())
+        {
+            debug!(target:"Participant", "Invalid Participant Found: {}", self.id);
+            Err(format!(
+                "Participant {} has load limits which allow no meetings",
+                self.id
+            ))
         } else {
             Ok(())
         }
@@ -54,12 +225,50 @@ where
 {
     /// Constructs a new Participant with the specified block_times.
     /// This indicates times when this participant *cannot* meet.
-    pub fn new(id: &str, blocked_times: Vec<TimeRange<N>>) -> Participant<N> {
+    pub fn new(id: &str, blocked_times: impl Into<TimeSet<N>>) -> Participant<N> {
         Participant {
             id: id.to_string(),
-            blocked_times,
+            blocked_times: blocked_times.into(),
+            preferences: vec![],
+            limits: LoadLimits::default(),
         }
     }
+
+    /// Adds a preference for, or against, meeting during `times`. The scheduler
+    /// looks for the placement where the fewest slots go against these, weighted
+    /// by `weight`.
+    pub fn with_preference(
+        mut self,
+        preference: Preference,
+        times: impl Into<TimeSet<N>>,
+        weight: u64,
+    ) -> Participant<N> {
+        self.preferences.push(SlotPreference {
+            times: times.into(),
+            preference,
+            weight,
+        });
+        self
+    }
+
+    /// Limits this participant to starting at most `count` meetings a day.
+    /// Needs the schedule to know how many slots make a day.
+    pub fn with_max_meetings_per_day(mut self, count: usize) -> Participant<N> {
+        self.limits.max_meetings_per_day = Some(count);
+        self
+    }
+
+    /// Limits this participant to at most `slots` of meetings back to back
+    pub fn with_max_consecutive(mut self, slots: N) -> Participant<N> {
+        self.limits.max_consecutive = Some(slots);
+        self
+    }
+
+    /// Requires at least `slots` free slots between this participant's meetings
+    pub fn with_min_break(mut self, slots: N) -> Participant<N> {
+        self.limits.min_break = Some(slots);
+        self
+    }
 }
 
 impl<N> Available<N> for Participant<N>
@@ -68,18 +277,15 @@ where
 {
     /// Gets the availability for this participant within the provided
     /// `available_times`.
-    fn get_availability(&self, available_times: &[TimeRange<N>]) -> Vec<TimeRange<N>> {
+    fn get_availability(&self, available_times: &TimeSet<N>) -> TimeSet<N> {
         if available_times.is_empty() {
             debug!(target: "Participant", "No available times, return empty;");
-            vec![]
+            TimeSet::new()
         } else if self.blocked_times.is_empty() {
             debug!(target: "Participant", "No blocked times, return all available;");
-            available_times.to_vec()
+            available_times.clone()
         } else {
-            available_times
-                .iter()
-                .blocks(self.blocked_times.iter())
-                .collect()
+            available_times.difference(&self.blocked_times)
         }
     }
 }
@@ -0,0 +1,104 @@
+use crate::time::{TimeSet, Validate};
+use log::debug;
+use num::{Integer, One};
+use std::fmt::{Debug, Display};
+
+/// A room, or anything else a meeting needs to book, such as a projector.
+/// Up to `capacity` meetings may use it at once, within its `availability`.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Resource<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    pub id: String,
+    /// What sort of resource this is, such as "room"
+    pub kind: String,
+    pub availability: TimeSet<N>,
+    #[cfg_attr(feature = "serde", serde(default = "one"))]
+    pub capacity: usize,
+}
+
+#[cfg(feature = "serde")]
+fn one() -> usize {
+    1
+}
+
+impl<N> Resource<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    /// Constructs a resource which one meeting at a time may use
+    pub fn new(id: &str, kind: &str, availability: impl Into<TimeSet<N>>) -> Resource<N> {
+        Resource {
+            id: id.to_string(),
+            kind: kind.to_string(),
+            availability: availability.into(),
+            capacity: 1,
+        }
+    }
+
+    /// Allows `capacity` meetings to use this resource at once
+    pub fn with_capacity(mut self, capacity: usize) -> Resource<N> {
+        self.capacity = capacity;
+        self
+    }
+}
+
+impl<N> Validate for Resource<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    fn validate(&self) -> Result<(), String> {
+        if self.capacity == 0 {
+            debug!(target: "Resource", "Invalid Resource Found: {}", self.id);
+            Err(format!("Resource {} has no capacity", self.id))
+        } else if let Some(Err(t)) = self
+            .availability
+            .iter()
+            .map(|t| t.validate())
+            .find(Result::is_err)
+        {
+            debug!(target: "Resource", "Invalid Resource Found: {}", self.id);
+            Err(format!(
+                "Resource {} has an invalid availability:\n\t{}",
+                self.id, t
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A resource a meeting needs one of
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ResourceRequest {
+    /// Any resource of this kind
+    Kind(String),
+    /// Any of the resources with these ids
+    OneOf(Vec<String>),
+}
+
+impl ResourceRequest {
+    /// Whether `resource` satisfies this request
+    pub fn accepts<N>(&self, resource: &Resource<N>) -> bool
+    where
+        N: Integer + One + Copy + Display + Debug,
+    {
+        match self {
+            ResourceRequest::Kind(kind) => resource.kind == *kind,
+            ResourceRequest::OneOf(ids) => ids.contains(&resource.id),
+        }
+    }
+}
+
+impl Display for ResourceRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            ResourceRequest::Kind(kind) => write!(f, "any {}", kind),
+            ResourceRequest::OneOf(ids) => write!(f, "one of {}", ids.join(", ")),
+        }
+    }
+}
@@ -1,11 +1,16 @@
-use crate::meeting::Meeting;
-use crate::time::{Available, Pigeons, TimeMerge, TimeRange, Validate, Windowed};
+use crate::constraint::Constraint;
+use crate::meeting::{Meeting, MeetingSeries, SeriesLink};
+use crate::participant::{LoadLimit, LoadLimits, Participant, SlotPreference};
+use crate::resource::Resource;
+use crate::solver::{Auto, Backtracking, Matching, Solver};
+use crate::time::{Alignment, Available, TimeRange, TimeSet, TimeWindow, Validate, Windowed};
 use core::fmt::{Debug, Display};
+use itertools::Itertools;
 use log::{debug, info, trace};
 use num::traits::AsPrimitive;
 use num::{CheckedAdd, CheckedSub, Integer, One};
 use std::cmp::Ordering;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::ops::ControlFlow;
 use thiserror::Error;
 
@@ -28,6 +33,14 @@ where
     NoSolutionWithinIteration(usize),
     #[error("Bad Request\n{error}")]
     InvalidData { error: String },
+    #[error("Meeting {meeting} has no time left between its earliest start and latest end")]
+    OutOfBounds { meeting: String },
+    #[error("Meeting {meeting} can not take place at its fixed time")]
+    FixedTimeUnavailable { meeting: String },
+    #[error("Meetings {meetings:?} can only take place in {slots} slots between them")]
+    HallViolation { meetings: Vec<String>, slots: usize },
+    #[error("No solution exists, mostly held back by {participant} taking {limit}")]
+    LoadLimitExceeded { participant: String, limit: String },
     #[cfg(feature = "rayon")]
     #[error("Thread Interrupted")]
     Interrupted,
@@ -87,7 +100,26 @@ where
     N: Integer + One + Copy + Display + Debug,
 {
     pub meetings: Vec<Meeting<N>>,
-    pub availability: Vec<TimeRange<N>>,
+    pub availability: TimeSet<N>,
+    /// The most meetings which may take place during any one slot
+    #[cfg_attr(feature = "serde", serde(rename = "maxConcurrent", default))]
+    pub max_concurrent: Option<usize>,
+    /// Rooms and other resources the meetings may book
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub resources: Vec<Resource<N>>,
+    /// Relations which must hold between the times of meetings
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub constraints: Vec<Constraint<N>>,
+    /// How many slots make up a day, counting from slot 0, for constraints about days
+    #[cfg_attr(feature = "serde", serde(rename = "slotsPerDay", default))]
+    pub slots_per_day: Option<N>,
+    /// Only start meetings on multiples of this many slots, unless they set their own
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub alignment: Option<N>,
+    /// Slots which no meeting may run into from the slot before, such as the start
+    /// of each day or of a lunch break
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub boundaries: BTreeSet<N>,
 }
 
 #[derive(Debug, Clone)]
@@ -99,6 +131,139 @@ where
     pub count: usize,
     pub results: Vec<MeetingTime<N>>,
     pub indices: Vec<usize>,
+    /// How many optional attendees are unable to attend their meeting, plus how much
+    /// the meeting times go against the attendees' preferences
+    pub cost: u64,
+}
+
+impl<N> ScheduleResult<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    /// A result holding `results`, for solvers which do not count iterations or costs
+    pub fn new(results: Vec<MeetingTime<N>>) -> ScheduleResult<N> {
+        ScheduleResult {
+            count: 0,
+            results,
+            indices: vec![],
+            cost: 0,
+        }
+    }
+}
+
+/// The most that could be scheduled, when not every meeting fits
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PartialSchedule<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    /// The placed meetings, and what they cost
+    pub result: ScheduleResult<N>,
+    /// Ids of the meetings which had to be left out
+    pub dropped: Vec<String>,
+    /// The combined priority of the placed meetings
+    pub priority: u64,
+}
+
+/// Why a schedule has no solution: meetings which can not all take place together,
+/// although any smaller part of them can
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Diagnosis<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    /// Ids of the meetings
+    pub meetings: Vec<String>,
+    /// The times they compete for
+    pub window: TimeSet<N>,
+    /// The slots they need between them, when that is more than `available`. Otherwise the
+    /// meetings fail on something besides room, such as constraints or load limits, and
+    /// neither count is given.
+    pub needed: Option<usize>,
+    /// The slots within `window`, when fewer than `needed`
+    pub available: Option<usize>,
+}
+
+/// A change which makes a schedule without a solution solvable
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum Relaxation<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    /// Free up `time` for the meeting, which these of its `participants` have blocked
+    Unblock {
+        meeting: String,
+        participants: Vec<String>,
+        time: TimeSet<N>,
+    },
+    /// Let the meeting take only `duration` slots
+    Shorten { meeting: String, duration: N },
+    /// Add `time` to the availability of the schedule
+    Extend { time: TimeRange<N> },
+    /// Leave the meeting out
+    Drop { meeting: String },
+}
+
+/// A relaxation which has been checked to make the schedule solvable, and what it costs:
+/// the number of slots it changes. Dropping a meeting costs its duration times its priority,
+/// which is not comparable to the others, so drops are only ranked among themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Suggestion<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    pub relaxation: Relaxation<N>,
+    pub cost: u64,
+}
+
+/// What leaving out a meeting of priority 1 costs, set far above what placements cost so
+/// that placing more meetings comes first
+const DROPPED: u64 = 1 << 32;
+
+/// What to avoid when rescheduling
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Disruption {
+    /// Every meeting which moves costs one
+    FewestMoves,
+    /// Every slot a meeting moves by costs one
+    LeastMovement,
+}
+
+/// A schedule found again after a change, compared to the one before it
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Rescheduled<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    pub result: ScheduleResult<N>,
+    /// Meetings still at the same time
+    pub kept: Vec<String>,
+    pub moved: Vec<MeetingMove<N>>,
+    /// Meetings which were scheduled before but are not any more, followed by new
+    /// meetings which could not be placed
+    pub dropped: Vec<String>,
+    /// Meetings which were not scheduled before
+    pub added: Vec<String>,
+}
+
+/// A meeting which had to move to another time
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MeetingMove<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    pub id: String,
+    pub from: TimeRange<N>,
+    pub to: TimeRange<N>,
 }
 
 #[derive(Debug, Clone)]
@@ -109,6 +274,30 @@ where
 {
     pub id: String,
     pub time: TimeRange<N>,
+    /// Ids of the resources booked for this meeting, in the order they were requested
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub resources: Vec<String>,
+    /// Optional attendees who are free at this time
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub attending: Vec<String>,
+    /// Optional attendees who are not
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub missing: Vec<String>,
+}
+
+impl<N> MeetingTime<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    pub fn new(id: &str, time: TimeRange<N>) -> MeetingTime<N> {
+        MeetingTime {
+            id: id.to_string(),
+            time,
+            resources: vec![],
+            attending: vec![],
+            missing: vec![],
+        }
+    }
 }
 
 impl<N> Validate for Schedule<N>
@@ -136,6 +325,53 @@ where
                 "Schedule contains invalid meeting:\n\t{}",
                 m.unwrap_err()
             ))
+        } else if self.max_concurrent == Some(0) {
+            Err("Schedule does not allow any concurrent meetings".to_string())
+        } else if let Some(r) = self
+            .resources
+            .iter()
+            .map(|r| r.validate())
+            .find(Result::is_err)
+        {
+            Err(format!(
+                "Schedule contains invalid resource:\n\t{}",
+                r.unwrap_err()
+            ))
+        } else if let Some((meeting, request)) = self.meetings.iter().find_map(|meeting| {
+            meeting
+                .resources
+                .iter()
+                .find(|request| !self.resources.iter().any(|r| request.accepts(r)))
+                .map(|request| (meeting, request))
+        }) {
+            Err(format!(
+                "Meeting {} needs {}, but there is no such resource",
+                meeting.id, request
+            ))
+        } else if let Some(id) = self
+            .constraints
+            .iter()
+            .flat_map(|c| {
+                let (first, second) = c.meetings();
+                [first, second]
+            })
+            .find(|id| !self.meetings.iter().any(|m| m.id == *id))
+        {
+            Err(format!("Constraint refers to unknown meeting {}", id))
+        } else if self.alignment == Some(<N>::zero()) {
+            Err("Schedule has an alignment of zero".to_string())
+        } else if self.slots_per_day == Some(<N>::zero()) {
+            Err("Schedule has no slots in a day".to_string())
+        } else if self.slots_per_day.is_none() && self.constraints.iter().any(|c| c.uses_days()) {
+            Err("Constraints about days need the number of slots per day".to_string())
+        } else if self.slots_per_day.is_none()
+            && self
+                .meetings
+                .iter()
+                .flat_map(|m| m.limits.values())
+                .any(|l| l.max_meetings_per_day.is_some())
+        {
+            Err("Limits on meetings a day need the number of slots per day".to_string())
         } else {
             Ok(())
         }
@@ -208,14 +444,16 @@ impl<
             meetings.push(u.arbitrary::<Meeting<N>>()?);
         }
 
-        let mut availability = u.arbitrary::<Vec<TimeRange<N>>>()?;
+        let mut availability = u.arbitrary::<TimeSet<N>>()?;
         if availability.is_empty() {
-            availability.push(u.arbitrary::<TimeRange<N>>()?);
+            availability.insert(u.arbitrary::<TimeRange<N>>()?);
         }
         Ok(Schedule::new(meetings, availability))
     }
 }
 
+/// A meeting prepared for solving: its availability within the schedule, and everything
+/// which decides where it may go and what that costs
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct MeetingScheduleInfo<N>
@@ -223,11 +461,213 @@ where
     N: Integer + Debug + Display + Debug + Copy,
 {
     id: String,
+    /// The shortest the meeting may be
     duration: N,
-    availability: Vec<TimeRange<N>>,
+    max_duration: Option<N>,
+    availability: TimeSet<N>,
+    participants: Vec<String>,
+    /// For every resource requested, the index of each resource which satisfies it
+    resources: Vec<Vec<usize>>,
+    optional_participants: Vec<Participant<N>>,
+    /// How many of the `optional_participants` must attend
+    min_attendance: Option<usize>,
+    preferences: Vec<SlotPreference<N>>,
+    series: Option<SeriesLink<N>>,
+    /// Whether the meeting is pinned to its only window
+    fixed: bool,
+    /// Windows only start on multiples of this, counted from the start of the day
+    alignment: N,
+    /// How many slots make up a day, which alignment is counted from
+    slots_per_day: Option<N>,
+    /// Where the meeting was placed before rescheduling, and what moving it costs
+    previous: Option<(TimeRange<N>, Disruption)>,
+    /// What leaving the meeting out costs, when it may be left out
+    dropping: Option<u64>,
 }
 
-type MeetingSchedule<N> = Vec<MeetingScheduleInfo<N>>;
+impl<N> MeetingScheduleInfo<N>
+where
+    N: Integer + One + Copy + Display + Debug + CheckedAdd + CheckedSub + AsPrimitive<usize>,
+{
+    /// The id of the meeting
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The times this meeting may take place within
+    pub fn availability(&self) -> &TimeSet<N> {
+        &self.availability
+    }
+
+    /// Ids of the attendees needed at this meeting
+    pub fn participants(&self) -> &[String] {
+        &self.participants
+    }
+
+    /// Whether this meeting takes a single slot, with nothing to weigh between its windows
+    /// and nothing tying it to other meetings besides conflicts
+    pub(crate) fn is_unit(&self) -> bool {
+        self.duration.is_one()
+            && self.max_duration.unwrap_or(self.duration).is_one()
+            && self.resources.is_empty()
+            && self.min_attendance.is_none()
+            && self.series.is_none()
+            && self.optional_participants.is_empty()
+            && self.preferences.is_empty()
+            && self.previous.is_none()
+            && self.dropping.is_none()
+    }
+
+    /// Meetings without any known attendees may conflict with any other meeting
+    fn conflicts_with_all(&self) -> bool {
+        self.participants.is_empty() && self.min_attendance.is_none()
+    }
+
+    /// Whether the two meetings may never overlap
+    pub fn conflicts(&self, other: &MeetingScheduleInfo<N>) -> bool {
+        self.conflicts_with_all()
+            || other.conflicts_with_all()
+            || self.shares_quorum(other)
+            || self
+                .participants
+                .iter()
+                .any(|p| other.participants.contains(p))
+    }
+
+    /// Whether both meetings need a quorum from some of the same people
+    fn shares_quorum(&self, other: &MeetingScheduleInfo<N>) -> bool {
+        self.min_attendance.is_some()
+            && other.min_attendance.is_some()
+            && self
+                .optional_participants
+                .iter()
+                .any(|p| other.optional_participants.iter().any(|o| o.id == p.id))
+    }
+
+    /// Every window this meeting could take place in, with the resources it would book.
+    /// The window it had before rescheduling comes first, then longer windows.
+    pub fn candidates<'a>(
+        &'a self,
+        resources: &'a [Resource<N>],
+        boundaries: &'a BTreeSet<N>,
+    ) -> impl Iterator<Item = (TimeRange<N>, Vec<usize>)> + 'a {
+        let mut durations = vec![self.max_duration.unwrap_or(self.duration)];
+        while let Some(&longest) = durations.last().filter(|&&d| d > self.duration) {
+            durations.push(longest - <N>::one());
+        }
+        let alignment = Alignment {
+            step: self.alignment,
+            slots_per_day: self.slots_per_day,
+            boundaries: Some(boundaries).filter(|_| !self.fixed),
+        };
+        let previous = self.previous.map(|(time, _)| time).filter(|time| {
+            alignment.align(time.start) == Some(time.start)
+                && durations.contains(&(time.end - time.start + <N>::one()))
+                && self.availability.contains(time)
+        });
+        previous
+            .into_iter()
+            .chain(
+                durations
+                    .into_iter()
+                    .flat_map(move |duration| self.windows(duration, boundaries))
+                    .filter(move |time| Some(*time) != previous),
+            )
+            .flat_map(move |time| {
+                let bookings = if self.resources.is_empty() {
+                    vec![vec![]]
+                } else {
+                    self.resources
+                        .iter()
+                        .map(|options| {
+                            options
+                                .iter()
+                                .copied()
+                                .filter(|&r| resources[r].availability.contains(&time))
+                                .collect::<Vec<_>>()
+                        })
+                        .multi_cartesian_product()
+                        .filter(|booking| booking.iter().all_unique())
+                        .collect()
+                };
+                bookings.into_iter().map(move |booking| (time, booking))
+            })
+    }
+
+    /// The windows of `duration` in this meeting's availability, which do not cross any of the
+    /// `boundaries` unless the meeting is pinned
+    fn windows<'a>(
+        &'a self,
+        duration: N,
+        boundaries: &'a BTreeSet<N>,
+    ) -> TimeWindow<'a, std::slice::Iter<'a, TimeRange<N>>, N> {
+        let windows = self
+            .availability
+            .iter()
+            .windowed(duration)
+            .aligned(self.alignment)
+            .in_days(self.slots_per_day);
+        if self.fixed {
+            windows
+        } else {
+            windows.split_at(boundaries)
+        }
+    }
+
+    /// The cost of placing this meeting at `time`: the number of optional attendees
+    /// who are blocked, plus the preferences of those who are not, plus every slot
+    /// it falls short of its `max_duration`, plus moving it when rescheduling.
+    /// Optional attendees who are needed in another meeting at the same time count as
+    /// missing too, once that meeting is placed.
+    pub fn cost(&self, time: &TimeRange<N>) -> u64 {
+        let (missing, attending): (Vec<_>, Vec<_>) = self
+            .optional_participants
+            .iter()
+            .partition(|p| p.blocked_times.overlaps(time));
+        let shortfall = self.max_duration.map_or(0, |max| {
+            (max - (time.end - time.start + <N>::one())).as_() as u64
+        });
+        let moved = match self.previous {
+            Some((previous, _)) if previous == *time => 0,
+            Some((_, Disruption::FewestMoves)) => 1,
+            Some((previous, Disruption::LeastMovement)) if previous.start > time.start => {
+                (previous.start - time.start).as_() as u64
+            }
+            Some((previous, Disruption::LeastMovement)) => {
+                (time.start - previous.start).as_() as u64
+            }
+            None => 0,
+        };
+        self.preferences
+            .iter()
+            .chain(attending.iter().flat_map(|p| p.preferences.iter()))
+            .fold(
+                (missing.len() as u64)
+                    .saturating_add(shortfall)
+                    .saturating_add(moved),
+                |acc, preference| acc.saturating_add(preference.cost(time)),
+            )
+    }
+
+    /// The lowest cost of any window this meeting could take place in
+    pub fn lowest_cost(&self, resources: &[Resource<N>], boundaries: &BTreeSet<N>) -> u64 {
+        if self.optional_participants.is_empty()
+            && self.preferences.is_empty()
+            && self.max_duration.unwrap_or(self.duration) == self.duration
+            && self.previous.is_none()
+        {
+            return 0;
+        }
+        self.candidates(resources, boundaries)
+            .map(|(time, _)| self.cost(&time))
+            .chain(self.dropping)
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+/// The meetings of a schedule, as prepared for a `Solver` by `Schedule::setup`
+pub type MeetingSchedule<N> = Vec<MeetingScheduleInfo<N>>;
 
 impl<
         #[cfg(all(not(feature = "rayon"), feature = "serde"))] N: Display
@@ -283,26 +723,165 @@ impl<
     > Schedule<N>
 {
     /// Constucts a new Schedule to be scheduled
-    pub fn new(meetings: Vec<Meeting<N>>, availability: Vec<TimeRange<N>>) -> Schedule<N> {
+    pub fn new(meetings: Vec<Meeting<N>>, availability: impl Into<TimeSet<N>>) -> Schedule<N> {
         Schedule {
             meetings,
-            availability,
+            availability: availability.into(),
+            max_concurrent: None,
+            resources: vec![],
+            constraints: vec![],
+            slots_per_day: None,
+            alignment: None,
+            boundaries: BTreeSet::new(),
         }
     }
 
+    /// Keeps meetings from running across any of `boundaries`, such as the start of each
+    /// day or of a lunch break
+    pub fn with_boundaries(mut self, boundaries: impl IntoIterator<Item = N>) -> Schedule<N> {
+        self.boundaries.extend(boundaries);
+        self
+    }
+
+    /// Only starts meetings on multiples of `alignment` slots, unless they set their own.
+    /// These are counted from the start of each day when `with_slots_per_day` is set,
+    /// and from each of the boundaries, whichever came last; otherwise from slot 0.
+    pub fn with_alignment(mut self, alignment: N) -> Schedule<N> {
+        self.alignment = Some(alignment);
+        self
+    }
+
+    /// The alignment `meeting` starts on
+    fn alignment_of(&self, meeting: &Meeting<N>) -> N {
+        meeting
+            .alignment
+            .or(self.alignment)
+            .unwrap_or_else(<N>::one)
+    }
+
+    /// Sets the relations which must hold between the times of meetings
+    pub fn with_constraints(mut self, constraints: Vec<Constraint<N>>) -> Schedule<N> {
+        self.constraints = constraints;
+        self
+    }
+
+    /// Sets how many slots make up a day, such as `SlotCalendar::slots_per_day()`
+    pub fn with_slots_per_day(mut self, slots_per_day: N) -> Schedule<N> {
+        self.slots_per_day = Some(slots_per_day);
+        self
+    }
+
+    /// Sets the rooms and other resources the meetings may book
+    pub fn with_resources(mut self, resources: Vec<Resource<N>>) -> Schedule<N> {
+        self.resources = resources;
+        self
+    }
+
+    /// Adds every occurrence of `series`, to be placed at the same time of day or week.
+    /// Fails with `InvalidData` when an occurrence would land past the largest `N`.
+    pub fn with_series(
+        mut self,
+        series: MeetingSeries<N>,
+    ) -> Result<Schedule<N>, ValidationError<N>> {
+        let meetings = series
+            .meetings()
+            .map_err(|error| ValidationError::InvalidData { error })?;
+        self.meetings.extend(meetings);
+        Ok(self)
+    }
+
+    /// Allows at most `limit` meetings during any one slot, even when they
+    /// share no participants
+    pub fn limit_concurrent(mut self, limit: usize) -> Schedule<N> {
+        self.max_concurrent = Some(limit);
+        self
+    }
+
     fn meeting_availability(&self) -> MeetingSchedule<N> {
         self.meetings
             .iter()
             .filter_map(|meeting| {
-                let meeting_availability = meeting.get_availability(&self.availability);
+                let mut meeting_availability = meeting.get_availability(&self.availability);
+                let resources = meeting
+                    .resources
+                    .iter()
+                    .map(|request| {
+                        let options = self
+                            .resources
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, resource)| request.accepts(resource))
+                            .map(|(index, _)| index)
+                            .collect::<Vec<_>>();
+
+                        // Only times some acceptable resource is free are useful
+                        meeting_availability = meeting_availability.intersection(
+                            &options
+                                .iter()
+                                .flat_map(|&r| self.resources[r].availability.iter())
+                                .collect(),
+                        );
+                        options
+                    })
+                    .collect::<Vec<_>>();
+                meeting_availability.retain(|&time| {
+                    (time.end - time.start)
+                        .checked_add(&<N>::one())
+                        .map(|n| n >= meeting.duration)
+                        .unwrap_or(true)
+                });
+
+                // A pinned meeting has only the one window
+                let (duration, max_duration) = match meeting.fixed_time {
+                    Some(fixed) => {
+                        meeting_availability = if meeting_availability.contains(&fixed) {
+                            fixed.into()
+                        } else {
+                            TimeSet::new()
+                        };
+                        (fixed.end - fixed.start + <N>::one(), None)
+                    }
+                    None => (meeting.duration, meeting.max_duration),
+                };
+
+                let fixed = meeting.fixed_time.is_some();
+                let alignment = if fixed {
+                    <N>::one()
+                } else {
+                    self.alignment_of(meeting)
+                };
+
+                // Only slots in some window between two boundaries are of any use
+                if !fixed && !self.boundaries.is_empty() {
+                    meeting_availability = meeting_availability
+                        .iter()
+                        .windowed(duration)
+                        .aligned(alignment)
+                        .in_days(self.slots_per_day)
+                        .split_at(&self.boundaries)
+                        .collect();
+                }
+
                 // Skip meetings with no availability
                 if meeting_availability.is_empty() {
                     None
                 } else {
                     Some(MeetingScheduleInfo {
                         id: meeting.id.clone(),
-                        duration: meeting.duration,
-                        availability: meeting.get_availability(&self.availability),
+                        duration,
+                        max_duration,
+                        availability: meeting_availability,
+                        participants: meeting.participants.clone(),
+                        resources,
+                        optional_participants: meeting.optional_participants.clone(),
+                        min_attendance: meeting.min_attendance,
+                        preferences: meeting.preferences.clone(),
+                        series: meeting.series.clone(),
+                        fixed,
+                        alignment,
+                        slots_per_day: self.slots_per_day,
+                        previous: None,
+                        dropping: None,
                     })
                 }
             })
@@ -317,9 +896,14 @@ impl<
                 if meeting_availability.is_empty() {
                     <N>::one()
                 } else {
+                    let alignment = Alignment {
+                        step: self.alignment_of(meeting),
+                        slots_per_day: self.slots_per_day,
+                        boundaries: Some(&self.boundaries),
+                    };
                     meeting_availability
                         .iter()
-                        .map(|t| t.end - t.start - (meeting.duration - <N>::one()))
+                        .map(|t| t.window_span(meeting.duration, &alignment))
                         .sum::<N>()
                 }
             })
@@ -328,19 +912,201 @@ impl<
     }
 
     pub fn setup(&self) -> Result<MeetingSchedule<N>, ValidationError<N>> {
+        self.prepare(true)
+    }
+
+    /// Prepares the meetings for solving. Only when `strict` are meetings which can never
+    /// be placed, or which can not all fit, turned down before the search.
+    fn prepare(&self, strict: bool) -> Result<MeetingSchedule<N>, ValidationError<N>> {
         if let Err(e) = self.validate() {
             return Err(ValidationError::InvalidData { error: e });
         }
+        let mut meeting_availability = self.meeting_availability();
+        if !strict {
+            // Pinned meetings which can not take place at their fixed time are left out
+            meeting_availability.retain(|m| {
+                self.meetings.iter().any(|meeting| {
+                    meeting.id == m.id
+                        && meeting
+                            .fixed_time
+                            .map(|fixed| {
+                                meeting
+                                    .duration_range()
+                                    .contains(&(fixed.end - fixed.start + <N>::one()))
+                            })
+                            .unwrap_or(true)
+                })
+            });
+            return Ok(self.clear_pinned(meeting_availability));
+        }
 
-        let meeting_availability = self.meeting_availability();
+        // Release times and deadlines must leave room for the meeting
+        if let Some(meeting) = self.meetings.iter().find(|meeting| {
+            (meeting.earliest_start.is_some() || meeting.latest_end.is_some())
+                && !meeting
+                    .within_bounds(&self.availability)
+                    .iter()
+                    .any(|time| {
+                        (time.end - time.start)
+                            .checked_add(&<N>::one())
+                            .map(|n| n >= meeting.duration)
+                            .unwrap_or(true)
+                    })
+        }) {
+            return Err(ValidationError::OutOfBounds {
+                meeting: meeting.id.clone(),
+            });
+        }
+
+        // No placement can keep an attendee below a run of slots shorter than the meeting
+        if let Some((participant, limit)) = self.meetings.iter().find_map(|meeting| {
+            meeting
+                .limits
+                .iter()
+                .find_map(|(id, limits)| match limits.max_consecutive {
+                    Some(max) if max < meeting.duration => Some((id, LoadLimit::Consecutive(max))),
+                    _ => None,
+                })
+        }) {
+            return Err(ValidationError::LoadLimitExceeded {
+                participant: participant.clone(),
+                limit: limit.to_string(),
+            });
+        }
+
+        // Pinned meetings must be able to take place at their fixed time
+        if let Some(meeting) = self
+            .meetings
+            .iter()
+            .find(|meeting| match meeting.fixed_time {
+                Some(fixed) => {
+                    !meeting
+                        .duration_range()
+                        .contains(&(fixed.end - fixed.start + <N>::one()))
+                        || !meeting_availability.iter().any(|m| m.id == meeting.id)
+                }
+                None => false,
+            })
+        {
+            return Err(ValidationError::FixedTimeUnavailable {
+                meeting: meeting.id.clone(),
+            });
+        }
 
-        let pigeon_holes = meeting_availability
+        let meeting_availability = self.clear_pinned(meeting_availability);
+
+        // Meetings without participants may conflict with any other meeting
+        let unknown = meeting_availability
+            .iter()
+            .filter(|m| m.conflicts_with_all())
+            .collect::<Vec<_>>();
+        if !unknown.is_empty() {
+            Schedule::pigeonhole(&unknown)?;
+        }
+
+        // Every meeting a participant attends must fit around each other
+        let participants = meeting_availability
+            .iter()
+            .flat_map(|m| m.participants.iter())
+            .collect::<BTreeSet<_>>();
+        for participant in participants {
+            Schedule::pigeonhole(
+                &meeting_availability
+                    .iter()
+                    .filter(|m| m.conflicts_with_all() || m.participants.contains(participant))
+                    .collect::<Vec<_>>(),
+            )?;
+        }
+
+        if let Some(limit) = self.max_concurrent {
+            Schedule::shared_pigeonhole(
+                &meeting_availability.iter().collect::<Vec<_>>(),
+                &self.availability,
+                limit,
+            )?;
+        }
+
+        // Meetings with no choice but one resource share its capacity
+        for (index, resource) in self.resources.iter().enumerate() {
+            let bound = meeting_availability
+                .iter()
+                .filter(|m| m.resources.iter().any(|options| options == &[index]))
+                .collect::<Vec<_>>();
+            if !bound.is_empty() {
+                Schedule::shared_pigeonhole(&bound, &resource.availability, resource.capacity)?;
+            }
+        }
+
+        Ok(meeting_availability)
+    }
+
+    /// Takes the times of pinned meetings out of the availability of meetings which conflict with them
+    fn clear_pinned(&self, mut meeting_availability: MeetingSchedule<N>) -> MeetingSchedule<N> {
+        let pinned = meeting_availability
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.fixed)
+            .map(|(index, m)| (index, m.availability.clone()))
+            .collect::<Vec<_>>();
+        for (index, fixed) in pinned {
+            for other in 0..meeting_availability.len() {
+                if other != index
+                    && meeting_availability[index].conflicts(&meeting_availability[other])
+                {
+                    let other = &mut meeting_availability[other];
+                    other.availability = other.availability.difference(&fixed);
+                    let duration = other.duration;
+                    other.availability.retain(|&time| {
+                        (time.end - time.start)
+                            .checked_add(&<N>::one())
+                            .map(|n| n >= duration)
+                            .unwrap_or(true)
+                    });
+                }
+            }
+        }
+
+        meeting_availability
+    }
+
+    /// Checks that `meetings` fit within their combined availability, where up to
+    /// `capacity` of them may take place at once during the `within` times
+    fn shared_pigeonhole(
+        meetings: &[&MeetingScheduleInfo<N>],
+        within: &TimeSet<N>,
+        capacity: usize,
+    ) -> Result<(), ValidationError<N>> {
+        let pigeons = meetings
+            .iter()
+            .try_fold(<N>::zero(), |acc, m| acc.checked_add(&m.duration));
+        let pigeon_holes = meetings
+            .iter()
+            .flat_map(|m| m.availability.iter())
+            .collect::<TimeSet<N>>()
+            .intersection(within)
+            .len_in_slots();
+        match (pigeons, pigeon_holes) {
+            (Some(pigeons), Some(pigeon_holes))
+                if pigeons.as_() > pigeon_holes.as_().saturating_mul(capacity) =>
+            {
+                Err(ValidationError::PigeonholeError {
+                    pigeons,
+                    pigeon_holes,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that `meetings`, which may not overlap each other, fit within their combined availability
+    fn pigeonhole(meetings: &[&MeetingScheduleInfo<N>]) -> Result<(), ValidationError<N>> {
+        let pigeon_holes = meetings
             .iter()
             .flat_map(|m| m.availability.iter())
-            .time_merge()
-            .count_pigeons();
+            .collect::<TimeSet<N>>()
+            .len_in_slots();
 
-        let mut pigeon_iter = meeting_availability.iter().map(|m| m.duration);
+        let mut pigeon_iter = meetings.iter().map(|m| m.duration);
 
         let pigeon_counter =
             pigeon_iter.try_fold(<N>::zero(), |acc, n| match acc.checked_add(&n) {
@@ -376,7 +1142,7 @@ impl<
                 _ => {}
             }
         }
-        Ok(meeting_availability)
+        Ok(())
     }
 
     /// Schedules the meetings within self.
@@ -471,11 +1237,18 @@ impl<
     /// make no distinction if `count` was reached, or if all solutions were checked before
     /// the solution was not reached.
     ///
+    /// When every meeting takes a single slot and has nothing else to weigh, the meetings
+    /// are matched to slots by `solver::Matching` instead, without iterating. A schedule
+    /// without a solution is then proven to have none, and still returns
+    /// `ValidationError::NoSolution`, even within a `count`. `Schedule::diagnose` tells which
+    /// meetings are to blame.
+    ///
     /// # NoSolution Error Example
     /// ```
     /// use zeitplan_libs::{
     ///     meeting::Meeting,
     ///     schedule::{Schedule, ValidationError},
+    ///     solver::Backtracking,
     ///     time::TimeRange,
     /// };
     ///
@@ -496,9 +1269,9 @@ impl<
     /// // iterations we provide it:
     /// let schedule = Schedule::new(meetings, available_slots);
     ///
-    /// // First - A single iteration is attempted
+    /// // First - A single iteration of the search is attempted
     /// assert!(matches!(
-    ///     schedule.schedule_meetings(Some(1), None, None),
+    ///     schedule.schedule_with(&Backtracking::new(Some(1))),
     ///     Err(ValidationError::NoSolutionWithinIteration(1))
     /// ));
     ///
@@ -513,6 +1286,504 @@ impl<
         count: Option<usize>,
         _per_thread: Option<usize>,
         _num_shuffles: Option<usize>,
+    ) -> Result<ScheduleResult<N>, ValidationError<N>> {
+        self.schedule_with(&Auto::new(
+            Backtracking::new(count).with_shuffles(_per_thread, _num_shuffles),
+        ))
+    }
+
+    /// Schedules the meetings with `solver`, instead of letting the library pick one
+    pub fn schedule_with(
+        &self,
+        solver: &impl Solver<N>,
+    ) -> Result<ScheduleResult<N>, ValidationError<N>> {
+        solver.solve(self, self.setup()?)
+    }
+
+    /// Schedules as many meetings as possible, or rather the highest combined `priority`,
+    /// leaving out the rest instead of failing. Among the ways to do so, the one with the
+    /// lowest cost is returned.
+    ///
+    /// ```
+    /// use zeitplan_libs::meeting::Meeting;
+    /// use zeitplan_libs::schedule::Schedule;
+    /// use zeitplan_libs::time::TimeRange;
+    ///
+    /// let schedule: Schedule<u8> = Schedule::new(
+    ///     vec![
+    ///         Meeting::new("review", vec![], 2).with_priority(2),
+    ///         Meeting::new("sync", vec![], 1),
+    ///         Meeting::new("demo", vec![], 1).with_priority(3),
+    ///     ],
+    ///     vec![TimeRange::new(0, 2)],
+    /// );
+    ///
+    /// // Only three slots: the review and demo matter more than the sync
+    /// let partial = schedule.schedule_partial(None, None, None).unwrap();
+    /// assert_eq!(partial.dropped, vec!["sync"]);
+    /// assert_eq!(partial.priority, 5);
+    /// ```
+    pub fn schedule_partial(
+        &self,
+        count: Option<usize>,
+        _per_thread: Option<usize>,
+        _num_shuffles: Option<usize>,
+    ) -> Result<PartialSchedule<N>, ValidationError<N>> {
+        let priority = |meeting: &Meeting<N>| meeting.priority.unwrap_or(1);
+        let setup = self.prepare_dropping()?;
+        let result = self.solve_dropping(
+            &Backtracking::new(count).with_shuffles(_per_thread, _num_shuffles),
+            setup,
+        )?;
+        let (placed, dropped): (Vec<_>, Vec<_>) = self
+            .meetings
+            .iter()
+            .partition(|meeting| result.results.iter().any(|m| m.id == meeting.id));
+        Ok(PartialSchedule {
+            dropped: dropped.iter().map(|meeting| meeting.id.clone()).collect(),
+            priority: placed
+                .iter()
+                .fold(0_u64, |acc, meeting| acc.saturating_add(priority(meeting))),
+            result,
+        })
+    }
+
+    /// Explains why the meetings can not be scheduled, by narrowing them down to a set which
+    /// can not all take place together, although they can without any one of them. Returns
+    /// `None` when a schedule exists. Every set tried is searched for up to `count` iterations,
+    /// and meetings it could not be decided for are kept in the set.
+    ///
+    /// ```
+    /// use zeitplan_libs::meeting::Meeting;
+    /// use zeitplan_libs::schedule::Schedule;
+    /// use zeitplan_libs::time::TimeRange;
+    ///
+    /// let schedule: Schedule<u8> = Schedule::new(
+    ///     vec![
+    ///         Meeting::new("kickoff", vec![TimeRange::new(2, 9)], 2),
+    ///         Meeting::new("retro", vec![TimeRange::new(2, 9)], 1),
+    ///         Meeting::new("sync", vec![], 1),
+    ///     ],
+    ///     vec![TimeRange::new(0, 9)],
+    /// );
+    ///
+    /// let diagnosis = schedule.diagnose(None).unwrap().unwrap();
+    /// assert_eq!(diagnosis.meetings, vec!["kickoff", "retro"]);
+    /// assert_eq!(diagnosis.window, vec![TimeRange::new(0, 1)].into());
+    /// assert_eq!((diagnosis.needed, diagnosis.available), (Some(3), Some(2)));
+    /// ```
+    pub fn diagnose(
+        &self,
+        count: Option<usize>,
+    ) -> Result<Option<Diagnosis<N>>, ValidationError<N>> {
+        // Matching, where it suits, proves which meetings are to blame right away
+        let outcome = match self.setup() {
+            Ok(setup) if Matching::suits(self, &setup) => Matching.solve(self, setup),
+            _ => self.schedule_meetings(count, None, None),
+        };
+        let mut core = match outcome {
+            Ok(_) => return Ok(None),
+            Err(ValidationError::HallViolation { meetings, .. }) => meetings,
+            Err(e @ ValidationError::InvalidData { .. })
+            | Err(e @ ValidationError::NoSolutionWithinIteration(_)) => return Err(e),
+            Err(_) => self.meetings.iter().map(|m| m.id.clone()).collect(),
+        };
+
+        // Leave out every meeting the rest still fail without
+        let mut index = 0;
+        while index < core.len() {
+            let mut rest = core.clone();
+            rest.remove(index);
+            if self.subset(&rest).infeasible(count) {
+                core = rest;
+            } else {
+                index += 1;
+            }
+        }
+
+        let subset = self.subset(&core);
+        let window = subset
+            .prepare(false)?
+            .iter()
+            .flat_map(|m| m.availability.iter())
+            .collect::<TimeSet<N>>();
+        let needed: usize = subset.meetings.iter().map(|m| m.duration.as_()).sum();
+        let available: usize = window.iter().map(|t| (t.end - t.start).as_() + 1).sum();
+        let short = needed > available;
+        Ok(Some(Diagnosis {
+            needed: Some(needed).filter(|_| short),
+            available: Some(available).filter(|_| short),
+            meetings: core,
+            window,
+        }))
+    }
+
+    /// Suggests the smallest changes which would let the meetings be scheduled, cheapest first
+    /// and with dropping a meeting last. Only the meetings found by `diagnose` are changed,
+    /// and every suggestion has been checked by scheduling again, for up to `count` iterations.
+    /// Returns nothing when a schedule exists.
+    ///
+    /// ```
+    /// use zeitplan_libs::meeting::Meeting;
+    /// use zeitplan_libs::schedule::{Relaxation, Schedule, Suggestion};
+    /// use zeitplan_libs::time::TimeRange;
+    ///
+    /// let schedule: Schedule<u8> = Schedule::new(
+    ///     vec![
+    ///         Meeting::new("kickoff", vec![TimeRange::new(3, 3)], 2),
+    ///         Meeting::new("retro", vec![TimeRange::new(2, 3)], 2),
+    ///     ],
+    ///     vec![TimeRange::new(0, 3)],
+    /// );
+    ///
+    /// // The retro takes the start, so the kickoff needs one more slot
+    /// let suggestions = schedule.suggest(None).unwrap();
+    /// assert_eq!(suggestions[0].cost, 1);
+    /// assert!(suggestions.contains(&Suggestion {
+    ///     relaxation: Relaxation::Shorten { meeting: "kickoff".to_string(), duration: 1 },
+    ///     cost: 1,
+    /// }));
+    /// assert!(suggestions.iter().any(|s| s.relaxation
+    ///     == Relaxation::Drop { meeting: "retro".to_string() }));
+    /// ```
+    pub fn suggest(&self, count: Option<usize>) -> Result<Vec<Suggestion<N>>, ValidationError<N>> {
+        let diagnosis = match self.diagnose(count)? {
+            Some(diagnosis) => diagnosis,
+            None => return Ok(vec![]),
+        };
+        let ids = self
+            .meetings
+            .iter()
+            .map(|m| m.id.clone())
+            .collect::<Vec<_>>();
+        let slots = |n: N| n.as_() as u64;
+        let solves = |schedule: Schedule<N>| schedule.schedule_meetings(count, None, None).is_ok();
+        // Extending by more than the diagnosed meetings take between them never helps
+        let needed = self
+            .meetings
+            .iter()
+            .filter(|m| diagnosis.meetings.contains(&m.id))
+            .map(|m| slots(m.duration))
+            .sum::<u64>();
+        let mut suggestions = vec![];
+
+        let core = self
+            .meetings
+            .iter()
+            .filter(|m| diagnosis.meetings.contains(&m.id));
+        for meeting in core {
+            let relaxed = |change: &dyn Fn(&mut Meeting<N>)| {
+                let mut schedule = self.subset(&ids);
+                if let Some(m) = schedule.meetings.iter_mut().find(|m| m.id == meeting.id) {
+                    change(m);
+                }
+                schedule
+            };
+            let pinned = meeting.fixed_time.is_some();
+
+            // The fewest blocked slots to free up for a window of the meeting's shortest
+            // duration, within its bounds, on its alignment and between any boundaries
+            let mut unblocks = meeting
+                .within_bounds(&self.availability)
+                .iter()
+                .windowed(meeting.duration)
+                .aligned(self.alignment_of(meeting))
+                .in_days(self.slots_per_day)
+                .split_at(&self.boundaries)
+                .map(|window| meeting.blocked_times.intersection(&window.into()))
+                .filter(|blocked| !blocked.is_empty())
+                .collect::<Vec<_>>();
+            let blocked_slots =
+                |time: &TimeSet<N>| time.iter().map(|t| slots(t.end - t.start) + 1).sum::<u64>();
+            unblocks.sort_by_key(|time| (blocked_slots(time), time.iter().next().map(|t| t.start)));
+            unblocks.dedup();
+            if let Some(time) = unblocks.into_iter().filter(|_| !pinned).find(|time| {
+                solves(relaxed(&|m| {
+                    m.blocked_times = m.blocked_times.difference(time);
+                    for blocked in m.participant_blocked_times.values_mut() {
+                        *blocked = blocked.difference(time);
+                    }
+                }))
+            }) {
+                // Without the blocked times of each attendee, any of them may be to blame
+                let participants = if meeting.participant_blocked_times.is_empty() {
+                    meeting.participants.clone()
+                } else {
+                    meeting
+                        .participant_blocked_times
+                        .iter()
+                        .filter(|(_, blocked)| !blocked.intersection(&time).is_empty())
+                        .map(|(id, _)| id.clone())
+                        .collect()
+                };
+                suggestions.push(Suggestion {
+                    cost: blocked_slots(&time),
+                    relaxation: Relaxation::Unblock {
+                        meeting: meeting.id.clone(),
+                        participants,
+                        time,
+                    },
+                });
+            }
+
+            // The least the meeting could be shortened by
+            let mut duration = meeting.duration;
+            while !pinned && duration > <N>::one() {
+                duration = duration - <N>::one();
+                if solves(relaxed(&|m| m.duration = duration)) {
+                    suggestions.push(Suggestion {
+                        cost: slots(meeting.duration - duration),
+                        relaxation: Relaxation::Shorten {
+                            meeting: meeting.id.clone(),
+                            duration,
+                        },
+                    });
+                    break;
+                }
+            }
+        }
+
+        // The least the availability could be extended by, before or after each of its ranges
+        for available in self.availability.iter() {
+            let after = |by: N| {
+                let end = available.end.checked_add(&by)?;
+                Some(TimeRange::new(available.end + <N>::one(), end))
+            };
+            let before = |by: N| {
+                let start = available.start.checked_sub(&by)?;
+                Some(TimeRange::new(start, available.start - <N>::one()))
+            };
+            for extend in [&after as &dyn Fn(N) -> Option<TimeRange<N>>, &before] {
+                let mut by = <N>::one();
+                while slots(by) <= needed {
+                    match extend(by) {
+                        Some(time) if !self.availability.overlaps(&time) => {
+                            let mut schedule = self.subset(&ids);
+                            schedule.availability = schedule.availability.union(&time.into());
+                            if solves(schedule) {
+                                suggestions.push(Suggestion {
+                                    cost: slots(by),
+                                    relaxation: Relaxation::Extend { time },
+                                });
+                                break;
+                            }
+                        }
+                        _ => break,
+                    }
+                    by += <N>::one();
+                }
+            }
+        }
+
+        for meeting in &diagnosis.meetings {
+            let rest = ids
+                .iter()
+                .filter(|id| *id != meeting)
+                .cloned()
+                .collect::<Vec<_>>();
+            if solves(self.subset(&rest)) {
+                let dropped = self.meetings.iter().find(|m| m.id == *meeting);
+                suggestions.push(Suggestion {
+                    cost: dropped
+                        .map(|m| slots(m.duration).saturating_mul(m.priority.unwrap_or(1)))
+                        .unwrap_or(0),
+                    relaxation: Relaxation::Drop {
+                        meeting: meeting.clone(),
+                    },
+                });
+            }
+        }
+
+        // Dropping a meeting is weighed on its own scale, so it always comes last
+        suggestions.sort_by_key(|suggestion| {
+            (
+                matches!(suggestion.relaxation, Relaxation::Drop { .. }),
+                suggestion.cost,
+            )
+        });
+        Ok(suggestions)
+    }
+
+    /// Prepares the meetings so that any of them may be left out, at a cost of `DROPPED`
+    /// times their priority. Meetings which can never take place are left out up front.
+    fn prepare_dropping(&self) -> Result<MeetingSchedule<N>, ValidationError<N>> {
+        let mut setup = self.prepare(false)?;
+        for info in setup.iter_mut() {
+            info.dropping = self
+                .meetings
+                .iter()
+                .find(|meeting| meeting.id == info.id)
+                .map(|meeting| meeting.priority.unwrap_or(1).saturating_mul(DROPPED));
+        }
+        Ok(setup)
+    }
+
+    /// Solves meetings prepared by `prepare_dropping`. Leaving meetings out is taken back
+    /// out of the cost of the result, since it is not a cost of the placements.
+    fn solve_dropping(
+        &self,
+        solver: &impl Solver<N>,
+        setup: MeetingSchedule<N>,
+    ) -> Result<ScheduleResult<N>, ValidationError<N>> {
+        let dropping = setup
+            .iter()
+            .map(|info| (info.id.clone(), info.dropping.unwrap_or(0)))
+            .collect::<Vec<_>>();
+        let mut result = solver.solve(self, setup)?;
+        result.cost = dropping
+            .iter()
+            .filter(|(id, _)| !result.results.iter().any(|m| m.id == *id))
+            .fold(result.cost, |cost, (_, dropped)| {
+                cost.saturating_sub(*dropped)
+            });
+        Ok(result)
+    }
+
+    /// This schedule, with only the meetings of `ids` and the constraints between them
+    fn subset(&self, ids: &[String]) -> Schedule<N> {
+        Schedule {
+            meetings: self
+                .meetings
+                .iter()
+                .filter(|m| ids.contains(&m.id))
+                .cloned()
+                .collect(),
+            availability: self.availability.clone(),
+            max_concurrent: self.max_concurrent,
+            resources: self.resources.clone(),
+            constraints: self
+                .constraints
+                .iter()
+                .filter(|c| {
+                    let (first, second) = c.meetings();
+                    ids.iter().any(|id| id == first) && ids.iter().any(|id| id == second)
+                })
+                .cloned()
+                .collect(),
+            slots_per_day: self.slots_per_day,
+            alignment: self.alignment,
+            boundaries: self.boundaries.clone(),
+        }
+    }
+
+    /// Whether this schedule is known to have no solution, within `count` iterations
+    fn infeasible(&self, count: Option<usize>) -> bool {
+        match self.schedule_meetings(count, None, None) {
+            Ok(_)
+            | Err(ValidationError::NoSolutionWithinIteration(_))
+            | Err(ValidationError::InvalidData { .. }) => false,
+            #[cfg(feature = "rayon")]
+            Err(ValidationError::Interrupted) => false,
+            Err(_) => true,
+        }
+    }
+
+    /// Schedules this (changed) schedule again, keeping meetings where they were placed in
+    /// `previous` wherever possible. `disruption` decides whether to move as few meetings as
+    /// possible, or to move them as little as possible. This cost adds to the usual cost
+    /// of a schedule, such as optional attendees who are missing. Meetings which can no
+    /// longer be placed are reported as `dropped`, rather than failing the schedule.
+    ///
+    /// ```
+    /// use zeitplan_libs::meeting::MeetingParticipants;
+    /// use zeitplan_libs::participant::Participant;
+    /// use zeitplan_libs::schedule::{Disruption, Schedule};
+    /// use zeitplan_libs::solver::Backtracking;
+    /// use zeitplan_libs::time::TimeRange;
+    ///
+    /// let meetings = |busy: Vec<TimeRange<u8>>| {
+    ///     vec![
+    ///         MeetingParticipants::new("1:1", vec![Participant::new("ann", busy)], 2).into(),
+    ///         MeetingParticipants::new("sync", vec![Participant::new("bob", vec![])], 2).into(),
+    ///     ]
+    /// };
+    ///
+    /// let previous = Schedule::new(meetings(vec![]), vec![TimeRange::new(0, 7)])
+    ///     .schedule_meetings(None, None, None)
+    ///     .unwrap();
+    ///
+    /// // ann can no longer make the start of the day
+    /// let changed = Schedule::new(meetings(vec![TimeRange::new(0, 0)]), vec![TimeRange::new(0, 7)]);
+    /// let rescheduled = changed
+    ///     .reschedule(&previous, Disruption::FewestMoves, &Backtracking::new(None))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(rescheduled.kept, vec!["sync"]);
+    /// assert_eq!(rescheduled.moved[0].id, "1:1");
+    /// assert_eq!(rescheduled.moved[0].to, TimeRange::new(1, 2));
+    /// ```
+    pub fn reschedule(
+        &self,
+        previous: &ScheduleResult<N>,
+        disruption: Disruption,
+        solver: &impl Solver<N>,
+    ) -> Result<Rescheduled<N>, ValidationError<N>> {
+        let mut setup = self.prepare_dropping()?;
+        for meeting in setup.iter_mut() {
+            meeting.previous = previous
+                .results
+                .iter()
+                .find(|m| m.id == meeting.id)
+                .map(|m| (m.time, disruption));
+        }
+        let result = self.solve_dropping(solver, setup)?;
+
+        let mut rescheduled = Rescheduled {
+            kept: vec![],
+            moved: vec![],
+            dropped: vec![],
+            added: vec![],
+            result,
+        };
+        for before in &previous.results {
+            match rescheduled
+                .result
+                .results
+                .iter()
+                .find(|m| m.id == before.id)
+            {
+                Some(after) if after.time == before.time => {
+                    rescheduled.kept.push(before.id.clone())
+                }
+                Some(after) => rescheduled.moved.push(MeetingMove {
+                    id: before.id.clone(),
+                    from: before.time,
+                    to: after.time,
+                }),
+                None => rescheduled.dropped.push(before.id.clone()),
+            }
+        }
+        rescheduled.added = rescheduled
+            .result
+            .results
+            .iter()
+            .filter(|after| !previous.results.iter().any(|before| before.id == after.id))
+            .map(|after| after.id.clone())
+            .collect();
+        let placed = &rescheduled.result.results;
+        let unplaced = self
+            .meetings
+            .iter()
+            .filter(|meeting| {
+                !previous
+                    .results
+                    .iter()
+                    .any(|before| before.id == meeting.id)
+            })
+            .filter(|meeting| !placed.iter().any(|m| m.id == meeting.id))
+            .map(|meeting| meeting.id.clone())
+            .collect::<Vec<_>>();
+        rescheduled.dropped.extend(unplaced);
+        Ok(rescheduled)
+    }
+
+    /// Searches for a solution to the meetings prepared by `setup`, as the `Backtracking` solver
+    pub(crate) fn search(
+        &self,
+        mut setup: MeetingSchedule<N>,
+        count: Option<usize>,
+        _per_thread: Option<usize>,
+        _num_shuffles: Option<usize>,
     ) -> Result<ScheduleResult<N>, ValidationError<N>> {
         /*
         TODO: We do it like this for now because we can *technically* setup
@@ -520,19 +1791,23 @@ impl<
         threads such as: Default Order, Default Order Reversed, Random Order,
         Random Order Reversed, Sort Order, Sort Order Reversed, etc.
         */
+        let alignment = |meeting: &MeetingScheduleInfo<N>| Alignment {
+            step: meeting.alignment,
+            slots_per_day: self.slots_per_day,
+            boundaries: Some(&self.boundaries),
+        };
         let meetings = {
-            let mut setup = self.setup()?;
             #[cfg(feature = "rayon")]
             setup.par_sort_unstable_by(|a, b| {
                 match (a
                     .availability
                     .iter()
-                    .map(|t| t.end - t.start - (a.duration - <N>::one()))
+                    .map(|t| t.window_span(a.duration, &alignment(a)))
                     .sum::<N>())
                 .cmp(
                     &b.availability
                         .iter()
-                        .map(|t| t.end - t.start - (b.duration - <N>::one()))
+                        .map(|t| t.window_span(b.duration, &alignment(b)))
                         .sum::<N>(),
                 ) {
                     Ordering::Equal => a.duration.cmp(&b.duration),
@@ -544,12 +1819,12 @@ impl<
                 match (a
                     .availability
                     .iter()
-                    .map(|t| t.end - t.start - (a.duration - <N>::one()))
+                    .map(|t| t.window_span(a.duration, &alignment(a)))
                     .sum::<N>())
                 .cmp(
                     &b.availability
                         .iter()
-                        .map(|t| t.end - t.start - (b.duration - <N>::one()))
+                        .map(|t| t.window_span(b.duration, &alignment(b)))
                         .sum::<N>(),
                 ) {
                     Ordering::Equal => a.duration.cmp(&b.duration),
@@ -557,7 +1832,7 @@ impl<
                 }
             });
 
-            setup
+            Schedule::group_series(setup)
         };
 
         #[cfg(feature = "serde")]
@@ -567,7 +1842,8 @@ impl<
 
         #[cfg(not(feature = "rayon"))]
         {
-            Schedule::schedule_setup(self.meetings.len(), &meetings, count)
+            self.schedule_setup(&meetings, count)
+                .map(|(result, _)| result)
         }
         #[cfg(feature = "rayon")]
         {
@@ -597,8 +1873,7 @@ impl<
                         debug!(target: "Schedule", meeting_config = log::as_serde!(meeting_configuration); "Searching solution in this configuration");
                         #[cfg(not(feature = "serde"))]
                         debug!(target: "Schedule", meeting_config = log::as_debug!(meeting_configuration); "Searching solution in this configuration");
-                        match Schedule::schedule_setup(
-                            self.meetings.len(),
+                        match self.schedule_setup(
                             &meeting_configuration,
                             iteration_count,
                             should_stop.clone(),
@@ -610,15 +1885,16 @@ impl<
                                     return None;
                                 }
                                 debug!(target: "Schedule", thread = "primary"; "Primary thread finished with result");
-                                Some(r)
+                                Some(r.map(|(result, _)| result))
                             },
-                            Ok(s) => {
+                            // Only a solution known to be the best is worth stopping for
+                            Ok((s, true)) => {
                                 debug!(target: "Schedule", thread = "worker"; "Worker thread found solution");
                                 Some(Ok(s))
                             },
-                            Err(ValidationError::NoSolution) => {
+                            Err(e @ (ValidationError::NoSolution | ValidationError::LoadLimitExceeded { .. })) => {
                                 debug!(target: "Schedule", thread = "worker"; "Worker thread identified a no solution result");
-                                Some(Err(ValidationError::NoSolution))
+                                Some(Err(e))
                             }
                             _ => {
                                 trace!(target: "Schedule", thread = "worker"; "Worker thread exited");
@@ -631,17 +1907,59 @@ impl<
         }
     }
 
+    /// Moves the occurrences of each series up behind the first of them, so placing the
+    /// first occurrence decides where the rest of the series goes
+    fn group_series(meetings: MeetingSchedule<N>) -> MeetingSchedule<N> {
+        let mut rest = meetings.into_iter().map(Some).collect::<Vec<_>>();
+        let mut grouped = Vec::with_capacity(rest.len());
+        for index in 0..rest.len() {
+            if let Some(meeting) = rest[index].take() {
+                let series = meeting.series.as_ref().map(|link| link.id.clone());
+                grouped.push(meeting);
+                if let Some(series) = series {
+                    grouped.extend(rest[index + 1..].iter_mut().filter_map(|other| {
+                        if other.as_ref()?.series.as_ref()?.id == series {
+                            other.take()
+                        } else {
+                            None
+                        }
+                    }));
+                }
+            }
+        }
+        grouped
+    }
+
+    /// Searches for the placement of `meetings`, in their given order, with the lowest cost.
+    ///
+    /// Partial placements which can not beat the best placement found so far are not
+    /// pursued. The search stops as soon as a placement reaches the lowest cost each meeting
+    /// could have on its own, so without any costs the first placement found is returned.
+    /// The returned flag tells whether the placement is known to be the best one.
     fn schedule_setup(
-        len: usize,
+        &self,
         meetings: &[MeetingScheduleInfo<N>],
         count: Option<usize>,
         #[cfg(feature = "rayon")] should_stop: std::sync::Arc<std::sync::atomic::AtomicBool>,
-    ) -> Result<ScheduleResult<N>, ValidationError<N>> {
+    ) -> Result<(ScheduleResult<N>, bool), ValidationError<N>> {
+        let len = self.meetings.len();
         let mut nth: usize = 1;
         let mut count_iter: usize = 0;
         let mut state: Vec<usize> = vec![0; len];
-        let mut solution: BTreeMap<InternalTimeRange<N>, String> = BTreeMap::new();
-        let mut last_key: Vec<TimeRange<N>> = Vec::with_capacity(len);
+        let mut solution = Placements::new(meetings, self);
+        let mut last_key: Vec<usize> = Vec::with_capacity(len);
+
+        // The lowest cost the meetings from each index onwards could add
+        let mut lower_bounds = meetings
+            .iter()
+            .map(|meeting| meeting.lowest_cost(&self.resources, &self.boundaries))
+            .collect::<Vec<_>>();
+        lower_bounds.push(0);
+        for index in (0..meetings.len()).rev() {
+            lower_bounds[index] = lower_bounds[index].saturating_add(lower_bounds[index + 1]);
+        }
+        let mut costs: Vec<u64> = vec![0; meetings.len()];
+        let mut best: Option<ScheduleResult<N>> = None;
 
         loop {
             #[cfg(feature = "rayon")]
@@ -651,31 +1969,61 @@ impl<
 
             if let Some(limit) = count {
                 if limit == count_iter {
-                    return Err(ValidationError::NoSolutionWithinIteration(limit));
+                    return match best {
+                        Some(result) => Ok((result, false)),
+                        None => Err(ValidationError::NoSolutionWithinIteration(limit)),
+                    };
                 }
             }
 
             count_iter += 1;
 
             if meetings.iter().enumerate().skip(nth - 1).all(
-                |(index, schedule_info)| match schedule_info.availability
-                    .iter()
-                    .windowed(schedule_info.duration)
+                |(index, schedule_info)| {
+                    let spent = costs[..index].iter().fold(0_u64, |acc, c| acc.saturating_add(*c));
+                    let within_budget = |cost: u64| match &best {
+                        Some(best) => spent
+                            .saturating_add(cost)
+                            .saturating_add(lower_bounds[index + 1]) < best.cost,
+                        None => true,
+                    };
+                    // Leaving the meeting out, when it may be, comes after every window
+                    let found = schedule_info
+                    .candidates(&self.resources, &self.boundaries)
+                    .map(Some)
+                    .chain(schedule_info.dropping.map(|_| None))
                     .enumerate()
                     .skip(state[index])
-                    .find(|(_time_index, time)| {
-                        !solution.contains_key::<InternalTimeRange<N>>(&time.into())
-                    }) {
-                    Some((i, time)) => {
-
-                        #[cfg(feature = "serde")]
-                        trace!(target: "Schedule", time = log::as_serde!(time); "Attempting to add new time for scheduling");
-                        #[cfg(not(feature = "serde"))]
-                        trace!(target: "Schedule", time = log::as_display!(time); "Attempting to add new time for scheduling");
-
+                    .find_map(|(i, candidate)| {
+                        let cost = match &candidate {
+                            Some((time, _)) => schedule_info
+                                .cost(time)
+                                .saturating_add(solution.absences(index, time)),
+                            None => schedule_info.dropping.unwrap_or(0),
+                        };
+                        let fits = within_budget(cost) && match &candidate {
+                            Some((time, booking)) => solution.fits(index, time, booking),
+                            None => true,
+                        };
+                        if fits {
+                            Some((i, candidate, cost))
+                        } else {
+                            None
+                        }
+                    });
+                    match found {
+                    Some((i, candidate, cost)) => {
                         state[index] = i;
-                        solution.insert(time.into(), schedule_info.id.to_owned());
-                        last_key.push(time);
+                        costs[index] = cost;
+                        if let Some((time, booking)) = candidate {
+                            #[cfg(feature = "serde")]
+                            trace!(target: "Schedule", time = log::as_serde!(time); "Attempting to add new time for scheduling");
+                            #[cfg(not(feature = "serde"))]
+                            trace!(target: "Schedule", time = log::as_display!(time); "Attempting to add new time for scheduling");
+
+                            solution.place(index, time, booking);
+                        }
+                        last_key.push(index);
                         nth += 1;
                         true
                     }
@@ -685,141 +2033,474 @@ impl<
                             state[index - 1] += 1;
                         }
 
-                        if let Some(last) = last_key.pop() {
+                        if let Some(last) = last_key.pop().and_then(|last| solution.remove(last)) {
                             #[cfg(feature = "serde")]
                             trace!(target: "Schedule", time = log::as_serde!(last); "Removing time from schedule and backtracing");
                             #[cfg(not(feature = "serde"))]
                             trace!(target: "Schedule", time = log::as_display!(last); "Removing time from schedule and backtracing");
-
-                            solution.remove::<InternalTimeRange<N>>(&last.into());
                         }
 
                         nth -= 1;
 
                         false
                     }
-                },
+                }},
             ) {
-                #[cfg(feature = "rayon")]
-                let as_ret = solution
-                    .into_par_iter()
-                    .map(|(k, v)| MeetingTime { id: v, time: TimeRange::from(k)} )
-                    .collect();
-                #[cfg(not(feature = "rayon"))]
-                let as_ret = solution
-                    .into_iter()
-                    .map(|(k, v)| MeetingTime { id: v, time: TimeRange::from(k)} )
-                    .collect();
+                let as_ret = solution.meeting_times();
+                let cost = costs.iter().fold(0_u64, |acc, c| acc.saturating_add(*c));
 
                 #[cfg(feature = "serde")]
                 {
-                    info!(target: "Schedule", schedule = log::as_serde!(as_ret); "Solution found");
+                    info!(target: "Schedule", schedule = log::as_serde!(as_ret), cost = cost; "Solution found");
                     debug!(target: "Schedule", state = log::as_serde!(state); "Indices used for solution");
                 }
                 #[cfg(not(feature = "serde"))]
                 {
-                    info!(target: "Schedule", schedule = log::as_debug!(as_ret); "Solution found");
+                    info!(target: "Schedule", schedule = log::as_debug!(as_ret), cost = cost; "Solution found");
                     debug!(target: "Schedule", state = log::as_debug!(state); "Indices used for solution");
                 }
 
-                #[cfg(feature = "rayon")]
-                // Stop processing on other threads
-                should_stop.store(true, std::sync::atomic::Ordering::SeqCst);
+                let result = ScheduleResult { count: count_iter, results: as_ret, indices: state.clone(), cost };
+                if cost <= lower_bounds[0] {
+                    #[cfg(feature = "rayon")]
+                    // Stop processing on other threads
+                    should_stop.store(true, std::sync::atomic::Ordering::SeqCst);
+
+                    return Ok((result, true));
+                }
+                best = Some(result);
 
-                return Ok(ScheduleResult { count: count_iter, results: as_ret, indices: state });
+                // Look for a cheaper placement of the last meeting, and so on
+                if let Some(last) = last_key.pop() {
+                    solution.remove(last);
+                    state[last] += 1;
+                }
+                nth -= 1;
             }
             if nth == 0 {
                 #[cfg(feature = "rayon")]
                 // Stop processing on other threads
                 should_stop.store(true, std::sync::atomic::Ordering::SeqCst);
 
-                return Err(ValidationError::NoSolution);
+                return match best {
+                    Some(mut result) => {
+                        result.count = count_iter;
+                        Ok((result, true))
+                    }
+                    // Point at the limit which turned away the most otherwise fitting placements
+                    None => match solution
+                        .violations
+                        .into_iter()
+                        .max_by_key(|(_, count)| *count)
+                    {
+                        Some(((participant, limit), _)) => {
+                            Err(ValidationError::LoadLimitExceeded {
+                                participant: participant.to_string(),
+                                limit: limit.to_string(),
+                            })
+                        }
+                        None => Err(ValidationError::NoSolution),
+                    },
+                };
             }
         }
     }
 }
 
-/// Inclusive [start, end] time range
-/// <N>: Any integer type
-#[derive(Debug, Copy, Clone, Eq)]
-struct InternalTimeRange<N>
+/// The times meetings have been placed at while searching for a solution
+struct Placements<'a, N>
 where
-    N: Integer + One + Copy,
+    N: Integer + One + Copy + Display + Debug,
 {
-    start: N,
-    end: N,
+    /// For every meeting, the meetings it may not overlap
+    conflicts: Vec<Vec<usize>>,
+    times: Vec<Option<TimeRange<N>>>,
+    /// For every meeting, the indices of the resources it booked
+    bookings: Vec<Vec<usize>>,
+    max_concurrent: Option<usize>,
+    meetings: &'a [MeetingScheduleInfo<N>],
+    resources: &'a [Resource<N>],
+    /// Every constraint between two meetings being placed, with the indices of both
+    constraints: Vec<(usize, usize, &'a Constraint<N>)>,
+    slots_per_day: Option<N>,
+    /// The strictest load limits of each attendee
+    limits: BTreeMap<&'a str, LoadLimits<N>>,
+    /// How often each load limit turned away a placement which fit otherwise
+    violations: BTreeMap<(&'a str, LoadLimit<N>), usize>,
 }
 
-impl<N> From<InternalTimeRange<N>> for TimeRange<N>
+impl<'a, N> Placements<'a, N>
 where
-    N: Integer + One + Copy + Display + Debug,
+    N: Integer + One + Copy + Display + Debug + CheckedAdd + CheckedSub + AsPrimitive<usize>,
 {
-    fn from(other: InternalTimeRange<N>) -> Self {
-        TimeRange::new(other.start, other.end)
+    fn new(meetings: &'a [MeetingScheduleInfo<N>], schedule: &'a Schedule<N>) -> Placements<'a, N> {
+        let index_of = |id: &str| meetings.iter().position(|m| m.id == id);
+        // Meetings which were skipped for having no availability are not held to anything
+        let constraints = schedule
+            .constraints
+            .iter()
+            .filter_map(|constraint| {
+                let (first, second) = constraint.meetings();
+                Some((index_of(first)?, index_of(second)?, constraint))
+            })
+            .collect();
+
+        let conflicts = meetings
+            .iter()
+            .enumerate()
+            .map(|(index, meeting)| {
+                meetings
+                    .iter()
+                    .enumerate()
+                    .filter(|(other_index, other)| {
+                        *other_index != index && meeting.conflicts(other)
+                    })
+                    .map(|(other_index, _)| other_index)
+                    .collect()
+            })
+            .collect();
+
+        let mut limits: BTreeMap<&str, LoadLimits<N>> = BTreeMap::new();
+        for (id, meeting_limits) in schedule.meetings.iter().flat_map(|m| m.limits.iter()) {
+            let merged = limits
+                .get(id.as_str())
+                .map(|limits| limits.strictest(meeting_limits))
+                .unwrap_or(*meeting_limits);
+            limits.insert(id, merged);
+        }
+
+        Placements {
+            conflicts,
+            times: vec![None; meetings.len()],
+            bookings: vec![vec![]; meetings.len()],
+            max_concurrent: schedule.max_concurrent,
+            meetings,
+            resources: &schedule.resources,
+            constraints,
+            slots_per_day: schedule.slots_per_day,
+            limits,
+            violations: BTreeMap::new(),
+        }
     }
-}
 
-impl<N> From<TimeRange<N>> for InternalTimeRange<N>
-where
-    N: Integer + One + Copy + Display + Debug,
-{
-    fn from(other: TimeRange<N>) -> Self {
-        InternalTimeRange::new(other.start, other.end)
+    /// The optional attendees of meeting `index` who are free at `time`: neither
+    /// blocked, nor needed in another meeting taking place alongside it
+    fn attending<'b>(
+        &'b self,
+        index: usize,
+        time: &'b TimeRange<N>,
+    ) -> impl Iterator<Item = &'b Participant<N>> + 'b {
+        self.meetings[index]
+            .optional_participants
+            .iter()
+            .filter(move |p| {
+                !p.blocked_times.overlaps(time)
+                    && !self.times.iter().enumerate().any(|(other, other_time)| {
+                        other != index
+                            && self.meetings[other].participants.contains(&p.id)
+                            && other_time
+                                .map(|other| other.start <= time.end && time.start <= other.end)
+                                .unwrap_or(false)
+                    })
+            })
     }
-}
 
-impl<N> From<&TimeRange<N>> for InternalTimeRange<N>
-where
-    N: Integer + One + Copy + Display + Debug,
-{
-    fn from(other: &TimeRange<N>) -> Self {
-        InternalTimeRange::new(other.start, other.end)
+    /// How many more optional attendees would miss a meeting once meeting `index` takes
+    /// place at `time`, because they are needed in another meeting alongside it. These
+    /// are the absences `MeetingScheduleInfo::cost` can not see on its own.
+    fn absences(&self, index: usize, time: &TimeRange<N>) -> u64 {
+        let overlaps = |a: &TimeRange<N>, b: &TimeRange<N>| a.start <= b.end && b.start <= a.end;
+        let placed = || {
+            self.times
+                .iter()
+                .enumerate()
+                .filter(move |(other, _)| *other != index)
+                .filter_map(|(other, other_time)| Some((other, (*other_time)?)))
+        };
+        // Optional attendees of this meeting who are needed elsewhere
+        let own = self.meetings[index]
+            .optional_participants
+            .iter()
+            .filter(|p| !p.blocked_times.overlaps(time))
+            .filter(|p| {
+                placed().any(|(other, other_time)| {
+                    overlaps(time, &other_time) && self.meetings[other].participants.contains(&p.id)
+                })
+            })
+            .count();
+        // Optional attendees of other meetings who are needed in this one, and who were
+        // free to attend until now
+        let others = placed()
+            .filter(|(_, other_time)| overlaps(time, other_time))
+            .map(|(other, other_time)| {
+                self.meetings[other]
+                    .optional_participants
+                    .iter()
+                    .filter(|p| !p.blocked_times.overlaps(&other_time))
+                    .filter(|p| self.meetings[index].participants.contains(&p.id))
+                    .filter(|p| {
+                        !placed().any(|(busy, busy_time)| {
+                            busy != other
+                                && overlaps(&other_time, &busy_time)
+                                && self.meetings[busy].participants.contains(&p.id)
+                        })
+                    })
+                    .count()
+            })
+            .sum::<usize>();
+        (own + others) as u64
     }
-}
 
-impl<N> InternalTimeRange<N>
-where
-    N: Integer + One + Copy,
-{
-    fn new(start: N, end: N) -> Self {
-        if start > end {
-            InternalTimeRange {
-                start: end,
-                end: start,
+    /// The first load limit of an attendee which placing meeting `index` at `time` would break
+    fn load_violation(&self, index: usize, time: &TimeRange<N>) -> Option<(&'a str, LoadLimit<N>)> {
+        let meetings = self.meetings;
+        meetings[index].participants.iter().find_map(|id| {
+            let (&id, limits) = self.limits.get_key_value(id.as_str())?;
+            let others = self
+                .times
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| {
+                    *other != index && meetings[*other].participants.iter().any(|p| p == id)
+                })
+                .filter_map(|(_, other)| *other)
+                .collect::<Vec<_>>();
+
+            if let Some(min_break) = limits.min_break {
+                if others.iter().any(|other| {
+                    if other.end < time.start {
+                        time.start - other.end - <N>::one() < min_break
+                    } else if time.end < other.start {
+                        other.start - time.end - <N>::one() < min_break
+                    } else {
+                        true
+                    }
+                }) {
+                    return Some((id, LoadLimit::Break(min_break)));
+                }
             }
-        } else {
-            InternalTimeRange { start, end }
-        }
+
+            if let Some(max_consecutive) = limits.max_consecutive {
+                // Grow the run of back to back meetings around this one
+                let mut run = *time;
+                while let Some(before) = others
+                    .iter()
+                    .find(|o| o.end.checked_add(&<N>::one()) == Some(run.start))
+                {
+                    run.start = before.start;
+                }
+                while let Some(after) = others
+                    .iter()
+                    .find(|o| run.end.checked_add(&<N>::one()) == Some(o.start))
+                {
+                    run.end = after.end;
+                }
+                // A run longer than `N` can hold is longer than any limit
+                if (run.end - run.start)
+                    .checked_add(&<N>::one())
+                    .map(|length| length > max_consecutive)
+                    .unwrap_or(true)
+                {
+                    return Some((id, LoadLimit::Consecutive(max_consecutive)));
+                }
+            }
+
+            match (limits.max_meetings_per_day, self.slots_per_day) {
+                (Some(max), Some(day))
+                    if others
+                        .iter()
+                        .filter(|o| o.start / day == time.start / day)
+                        .count()
+                        >= max =>
+                {
+                    Some((id, LoadLimit::MeetingsPerDay(max)))
+                }
+                _ => None,
+            }
+        })
     }
-}
 
-impl<N> Ord for InternalTimeRange<N>
-where
-    N: Integer + Copy,
-{
-    fn cmp(&self, other: &Self) -> Ordering {
-        match self.start.cmp(&other.start) {
-            Ordering::Less if self.end < other.start => Ordering::Less,
-            Ordering::Greater if self.start > other.end => Ordering::Greater,
-            _ => Ordering::Equal,
+    /// Whether meeting `index` may be placed at `time`, booking the resources of `booking`
+    fn fits(&mut self, index: usize, time: &TimeRange<N>, booking: &[usize]) -> bool {
+        let overlaps = |other: &TimeRange<N>| other.start <= time.end && time.start <= other.end;
+
+        if self.conflicts[index]
+            .iter()
+            .filter_map(|&other| self.times[other].as_ref())
+            .any(overlaps)
+        {
+            return false;
+        }
+
+        // Constraints with meetings already placed must hold
+        if self.constraints.iter().any(|&(first, second, constraint)| {
+            let placed = |other: usize| self.times[other].as_ref();
+            match (first == index, second == index) {
+                (true, false) => placed(second)
+                    .map(|other| !constraint.holds(time, other, self.slots_per_day))
+                    .unwrap_or(false),
+                (false, true) => placed(first)
+                    .map(|other| !constraint.holds(other, time, self.slots_per_day))
+                    .unwrap_or(false),
+                _ => false,
+            }
+        }) {
+            return false;
+        }
+
+        // Occurrences of a series share one time, give or take their deviation
+        let meeting = &self.meetings[index];
+        if let Some(link) = &meeting.series {
+            // A slack past the largest `N` lets the occurrences sit anywhere
+            let slack = link.max_deviation.checked_add(&link.max_deviation);
+            if self.times.iter().enumerate().any(|(other, other_time)| {
+                match (&self.meetings[other].series, other_time) {
+                    (Some(other_link), Some(other_time)) if other_link.id == link.id => {
+                        match (
+                            time.start.checked_add(&other_link.shift),
+                            other_time.start.checked_add(&link.shift),
+                        ) {
+                            (Some(a), Some(b)) if a >= b => slack.is_some_and(|s| a - b > s),
+                            (Some(a), Some(b)) => slack.is_some_and(|s| b - a > s),
+                            _ => true,
+                        }
+                    }
+                    _ => false,
+                }
+            }) {
+                return false;
+            }
+        }
+
+        // Enough people must be free to attend a quorum meeting, including those
+        // already placed alongside this one
+        if let Some(min_attendance) = meeting.min_attendance {
+            if self.attending(index, time).count() < min_attendance {
+                return false;
+            }
+        }
+        if !meeting.participants.is_empty()
+            && self.times.iter().enumerate().any(|(other, other_time)| {
+                match (self.meetings[other].min_attendance, other_time) {
+                    (Some(min_attendance), Some(other_time)) if overlaps(other_time) => {
+                        self.attending(other, other_time)
+                            .filter(|p| !meeting.participants.contains(&p.id))
+                            .count()
+                            < min_attendance
+                    }
+                    _ => false,
+                }
+            })
+        {
+            return false;
+        }
+
+        // Whether fewer than `limit` of the placed meetings picked by `filter` overlap at any one slot
+        let below = |limit: usize, filter: &dyn Fn(usize) -> bool| {
+            let concurrent = self
+                .times
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| filter(*other))
+                .filter_map(|(_, other)| other.filter(overlaps))
+                .map(|other| TimeRange::new(other.start.max(time.start), other.end.min(time.end)))
+                .collect::<Vec<_>>();
+            concurrent.len() < limit || max_overlap(&concurrent) < limit
+        };
+
+        if !(self
+            .max_concurrent
+            .map(|limit| below(limit, &|_| true))
+            .unwrap_or(true)
+            && booking.iter().all(|&resource| {
+                below(self.resources[resource].capacity, &|other| {
+                    self.bookings[other].contains(&resource)
+                })
+            }))
+        {
+            return false;
+        }
+
+        // Attendees' load limits come last, so only placements held back by them alone count
+        match self.load_violation(index, time) {
+            Some(violation) => {
+                *self.violations.entry(violation).or_insert(0) += 1;
+                false
+            }
+            None => true,
         }
     }
-}
 
-impl<N> PartialOrd for InternalTimeRange<N>
-where
-    N: Integer + Copy,
-{
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    fn place(&mut self, index: usize, time: TimeRange<N>, booking: Vec<usize>) {
+        self.times[index] = Some(time);
+        self.bookings[index] = booking;
+    }
+
+    fn remove(&mut self, index: usize) -> Option<TimeRange<N>> {
+        self.bookings[index].clear();
+        self.times[index].take()
+    }
+
+    /// Every placed meeting, ordered by time
+    fn meeting_times(&self) -> Vec<MeetingTime<N>> {
+        let mut placed = self
+            .times
+            .iter()
+            .zip(&self.bookings)
+            .enumerate()
+            .filter_map(|(index, (time, booking))| {
+                let time = (*time)?;
+                let meeting = &self.meetings[index];
+                // Optional attendees can not be in two places at once either
+                let attending = self
+                    .attending(index, &time)
+                    .map(|p| p.id.clone())
+                    .collect::<Vec<_>>();
+                let missing = meeting
+                    .optional_participants
+                    .iter()
+                    .filter(|p| !attending.contains(&p.id))
+                    .map(|p| p.id.clone())
+                    .collect();
+                Some(MeetingTime {
+                    id: meeting.id.clone(),
+                    time,
+                    resources: booking
+                        .iter()
+                        .map(|&resource| self.resources[resource].id.clone())
+                        .collect(),
+                    attending,
+                    missing,
+                })
+            })
+            .collect::<Vec<_>>();
+        placed.sort_by_key(|meeting| (meeting.time.start, meeting.time.end));
+        placed
     }
 }
 
-impl<N> PartialEq for InternalTimeRange<N>
+/// The most `times` overlapping at any one slot
+fn max_overlap<N>(times: &[TimeRange<N>]) -> usize
 where
-    N: Integer + Copy,
+    N: Integer + One + Copy + Display + Debug,
 {
-    fn eq(&self, other: &Self) -> bool {
-        self.start == other.start && self.end == other.end
-    }
+    // Starts sort before ends of the same slot, as both ends are inclusive
+    let mut edges = times
+        .iter()
+        .flat_map(|time| [(time.start, false), (time.end, true)])
+        .collect::<Vec<_>>();
+    edges.sort();
+
+    edges
+        .into_iter()
+        .scan(0_usize, |open, (_, end)| {
+            if end {
+                *open -= 1;
+            } else {
+                *open += 1;
+            }
+            Some(*open)
+        })
+        .max()
+        .unwrap_or(0)
 }
@@ -0,0 +1,498 @@
+use crate::schedule::{
+    MeetingSchedule, MeetingScheduleInfo, MeetingTime, Schedule, ScheduleResult, ValidationError,
+};
+use crate::time::TimeRange;
+use core::fmt::{Debug, Display};
+use num::traits::AsPrimitive;
+use num::{CheckedAdd, CheckedSub, Integer, One};
+use std::collections::{BTreeSet, VecDeque};
+
+/// Searches for placements of the meetings prepared by `Schedule::setup`.
+///
+/// ```
+/// use zeitplan_libs::meeting::Meeting;
+/// use zeitplan_libs::schedule::{
+///     MeetingSchedule, MeetingTime, Schedule, ScheduleResult, ValidationError,
+/// };
+/// use zeitplan_libs::solver::Solver;
+/// use zeitplan_libs::time::TimeRange;
+///
+/// /// Places every meeting at the start of its first window, if that happens to work
+/// struct Eager;
+///
+/// impl Solver<u8> for Eager {
+///     fn solve(
+///         &self,
+///         schedule: &Schedule<u8>,
+///         meetings: MeetingSchedule<u8>,
+///     ) -> Result<ScheduleResult<u8>, ValidationError<u8>> {
+///         let mut placed: Vec<(usize, TimeRange<u8>)> = vec![];
+///         for (index, meeting) in meetings.iter().enumerate() {
+///             let (time, _) = meeting
+///                 .candidates(&schedule.resources, &schedule.boundaries)
+///                 .next()
+///                 .ok_or(ValidationError::NoSolution)?;
+///             if placed.iter().any(|(other, t)| {
+///                 meeting.conflicts(&meetings[*other]) && t.start <= time.end && time.start <= t.end
+///             }) {
+///                 return Err(ValidationError::NoSolution);
+///             }
+///             placed.push((index, time));
+///         }
+///         Ok(ScheduleResult::new(
+///             placed
+///                 .into_iter()
+///                 .map(|(index, time)| MeetingTime::new(meetings[index].id(), time))
+///                 .collect(),
+///         ))
+///     }
+/// }
+///
+/// let schedule = Schedule::new(
+///     vec![Meeting::new("standup", vec![], 1)],
+///     vec![TimeRange::new(0, 3)],
+/// );
+/// let result = schedule.schedule_with(&Eager).unwrap();
+/// assert_eq!(result.results[0].time, TimeRange::new(0, 0));
+/// ```
+pub trait Solver<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    /// Places the `meetings` prepared from `schedule`, or explains why they can not be
+    fn solve(
+        &self,
+        schedule: &Schedule<N>,
+        meetings: MeetingSchedule<N>,
+    ) -> Result<ScheduleResult<N>, ValidationError<N>>;
+}
+
+/// Depth first search through the windows of each meeting, which backtracks on conflicts
+/// and prunes placements which can not beat the cheapest one found so far. With the
+/// `rayon` feature, shuffled orders of the meetings are searched alongside.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Backtracking {
+    /// Gives up after this many iterations
+    pub count: Option<usize>,
+    /// Iterations for each shuffled order
+    pub per_thread: Option<usize>,
+    /// How many shuffled orders to search
+    pub num_shuffles: Option<usize>,
+}
+
+impl Backtracking {
+    /// Searches for at most `count` iterations, or until done when `None`
+    pub fn new(count: Option<usize>) -> Backtracking {
+        Backtracking {
+            count,
+            ..Backtracking::default()
+        }
+    }
+
+    /// Sets how long, and how many, shuffled orders are searched with the `rayon` feature
+    pub fn with_shuffles(
+        mut self,
+        per_thread: Option<usize>,
+        num_shuffles: Option<usize>,
+    ) -> Backtracking {
+        self.per_thread = per_thread;
+        self.num_shuffles = num_shuffles;
+        self
+    }
+}
+
+impl<
+        #[cfg(all(not(feature = "rayon"), feature = "serde"))] N: Display
+            + Debug
+            + Integer
+            + One
+            + Clone
+            + Copy
+            + AsPrimitive<usize>
+            + std::iter::Sum
+            + std::ops::AddAssign
+            + CheckedSub
+            + CheckedAdd
+            + serde::Serialize,
+        #[cfg(all(not(feature = "rayon"), not(feature = "serde")))] N: Display
+            + Debug
+            + Integer
+            + One
+            + Clone
+            + AsPrimitive<usize>
+            + Copy
+            + std::iter::Sum
+            + std::ops::AddAssign
+            + CheckedSub
+            + CheckedAdd,
+        #[cfg(all(feature = "rayon", feature = "serde"))] N: Display
+            + Debug
+            + Integer
+            + One
+            + Clone
+            + Copy
+            + AsPrimitive<usize>
+            + std::iter::Sum
+            + std::ops::AddAssign
+            + CheckedSub
+            + CheckedAdd
+            + std::marker::Send
+            + std::marker::Sync
+            + serde::Serialize,
+        #[cfg(all(feature = "rayon", not(feature = "serde")))] N: Display
+            + Debug
+            + Integer
+            + One
+            + AsPrimitive<usize>
+            + Clone
+            + Copy
+            + std::iter::Sum
+            + std::ops::AddAssign
+            + CheckedSub
+            + CheckedAdd
+            + std::marker::Sync
+            + std::marker::Send,
+    > Solver<N> for Backtracking
+{
+    fn solve(
+        &self,
+        schedule: &Schedule<N>,
+        meetings: MeetingSchedule<N>,
+    ) -> Result<ScheduleResult<N>, ValidationError<N>> {
+        schedule.search(meetings, self.count, self.per_thread, self.num_shuffles)
+    }
+}
+
+/// Matches meetings to slots with Hopcroft–Karp. This suits problems where every meeting
+/// takes a single slot, and meetings which conflict all conflict with each other, such as
+/// meetings without participants. These are solved in polynomial time, and when there is
+/// no solution, the meetings which can not all be placed are returned as proof.
+///
+/// ```
+/// use zeitplan_libs::meeting::Meeting;
+/// use zeitplan_libs::schedule::{Schedule, ValidationError};
+/// use zeitplan_libs::solver::Matching;
+/// use zeitplan_libs::time::TimeRange;
+///
+/// let schedule: Schedule<u8> = Schedule::new(
+///     vec![
+///         Meeting::new("1", vec![TimeRange::new(2, 5)], 1),
+///         Meeting::new("2", vec![TimeRange::new(2, 5)], 1),
+///         Meeting::new("3", vec![TimeRange::new(2, 5)], 1),
+///         Meeting::new("4", vec![], 1),
+///     ],
+///     vec![TimeRange::new(0, 5)],
+/// );
+///
+/// match schedule.schedule_with(&Matching) {
+///     Err(ValidationError::HallViolation { meetings, slots }) => {
+///         assert_eq!(meetings.len(), 3);
+///         assert_eq!(slots, 2);
+///     }
+///     _ => panic!("Three meetings fit into two slots"),
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Matching;
+
+impl Matching {
+    /// Whether the `meetings` prepared from `schedule` are a matching problem
+    pub fn suits<N>(schedule: &Schedule<N>, meetings: &[MeetingScheduleInfo<N>]) -> bool
+    where
+        N: Integer + One + Copy + Display + Debug + CheckedAdd + CheckedSub + AsPrimitive<usize>,
+    {
+        schedule.constraints.is_empty()
+            && schedule.max_concurrent.is_none()
+            && schedule.meetings.iter().all(|m| m.limits.is_empty())
+            && meetings.iter().all(MeetingScheduleInfo::is_unit)
+            && Matching::groups(meetings).is_some()
+    }
+
+    /// Splits the meetings into groups which conflict among themselves and with no other
+    /// meeting, or `None` when their conflicts do not fall apart like this
+    fn groups<N>(meetings: &[MeetingScheduleInfo<N>]) -> Option<Vec<Vec<usize>>>
+    where
+        N: Integer + One + Copy + Display + Debug + CheckedAdd + CheckedSub + AsPrimitive<usize>,
+    {
+        let mut groups: Vec<Vec<usize>> = vec![];
+        for (index, meeting) in meetings.iter().enumerate() {
+            let mut conflicting = groups.iter().enumerate().filter(|(_, group)| {
+                group
+                    .iter()
+                    .any(|&other| meeting.conflicts(&meetings[other]))
+            });
+            match (conflicting.next(), conflicting.next()) {
+                (None, _) => groups.push(vec![index]),
+                (Some((group, members)), None)
+                    if members
+                        .iter()
+                        .all(|&other| meeting.conflicts(&meetings[other])) =>
+                {
+                    groups[group].push(index)
+                }
+                _ => return None,
+            }
+        }
+        Some(groups)
+    }
+}
+
+impl<N> Solver<N> for Matching
+where
+    N: Integer + One + Copy + Display + Debug + CheckedAdd + CheckedSub + AsPrimitive<usize>,
+{
+    fn solve(
+        &self,
+        schedule: &Schedule<N>,
+        meetings: MeetingSchedule<N>,
+    ) -> Result<ScheduleResult<N>, ValidationError<N>> {
+        let groups = match Matching::groups(&meetings) {
+            Some(groups) if Matching::suits(schedule, &meetings) => groups,
+            _ => return Err(ValidationError::InvalidData {
+                error:
+                    "Only meetings of a single slot, conflicting in separate groups, can be matched"
+                        .to_string(),
+            }),
+        };
+
+        let mut result = ScheduleResult::new(vec![]);
+        result.indices = vec![0; meetings.len()];
+        for group in groups {
+            let windows = group
+                .iter()
+                .map(|&index| {
+                    meetings[index]
+                        .candidates(&schedule.resources, &schedule.boundaries)
+                        .map(|(time, _)| time)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            // Meetings of a group can not share a slot, so each slot is taken at most once
+            let slots = windows
+                .iter()
+                .flatten()
+                .map(|time| time.start)
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect::<Vec<_>>();
+            let adjacency = windows
+                .iter()
+                .map(|times| {
+                    times
+                        .iter()
+                        .filter_map(|time| slots.binary_search(&time.start).ok())
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+
+            let (meeting_slot, slot_meeting) = hopcroft_karp(&adjacency, slots.len());
+            if let Some(unmatched) = meeting_slot.iter().position(Option::is_none) {
+                let (hall, neighbours) = hall_violation(&adjacency, &slot_meeting, unmatched);
+                let mut hall = hall
+                    .into_iter()
+                    .map(|m| meetings[group[m]].id().to_string())
+                    .collect::<Vec<_>>();
+                hall.sort();
+                return Err(ValidationError::HallViolation {
+                    meetings: hall,
+                    slots: neighbours,
+                });
+            }
+
+            for (m, slot) in meeting_slot.into_iter().enumerate() {
+                let slot = slot.map(|slot| slots[slot]);
+                if let Some(window) = windows[m].iter().position(|time| Some(time.start) == slot) {
+                    let time: TimeRange<N> = windows[m][window];
+                    result.indices[group[m]] = window;
+                    result
+                        .results
+                        .push(MeetingTime::new(meetings[group[m]].id(), time));
+                }
+            }
+        }
+        result.results.sort_by_key(|meeting| meeting.time.start);
+        Ok(result)
+    }
+}
+
+/// A maximum matching of meetings to the slots in their `adjacency`, as the slot of every
+/// meeting and the meeting of every slot
+fn hopcroft_karp(
+    adjacency: &[Vec<usize>],
+    slots: usize,
+) -> (Vec<Option<usize>>, Vec<Option<usize>>) {
+    let mut meeting_slot = vec![None; adjacency.len()];
+    let mut slot_meeting = vec![None; slots];
+    loop {
+        // Layer the meetings by their distance from an unmatched meeting along alternating paths
+        let mut layers = vec![usize::MAX; adjacency.len()];
+        let mut queue = meeting_slot
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| slot.is_none())
+            .map(|(m, _)| m)
+            .collect::<VecDeque<_>>();
+        for &m in &queue {
+            layers[m] = 0;
+        }
+        let mut augmentable = false;
+        while let Some(m) = queue.pop_front() {
+            for &slot in &adjacency[m] {
+                match slot_meeting[slot] {
+                    None => augmentable = true,
+                    Some(next) if layers[next] == usize::MAX => {
+                        layers[next] = layers[m] + 1;
+                        queue.push_back(next);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if !augmentable {
+            return (meeting_slot, slot_meeting);
+        }
+
+        for m in 0..adjacency.len() {
+            if meeting_slot[m].is_none() {
+                augment(
+                    m,
+                    adjacency,
+                    &mut layers,
+                    &mut meeting_slot,
+                    &mut slot_meeting,
+                );
+            }
+        }
+    }
+}
+
+/// Follows the layers from meeting `m` to a free slot, flipping the matching along the way
+fn augment(
+    m: usize,
+    adjacency: &[Vec<usize>],
+    layers: &mut [usize],
+    meeting_slot: &mut [Option<usize>],
+    slot_meeting: &mut [Option<usize>],
+) -> bool {
+    for &slot in &adjacency[m] {
+        let free = match slot_meeting[slot] {
+            None => true,
+            Some(next) => {
+                layers[next] == layers[m] + 1
+                    && augment(next, adjacency, layers, meeting_slot, slot_meeting)
+            }
+        };
+        if free {
+            meeting_slot[m] = Some(slot);
+            slot_meeting[slot] = Some(m);
+            return true;
+        }
+    }
+    layers[m] = usize::MAX;
+    false
+}
+
+/// The meetings reachable from the `unmatched` meeting along alternating paths of a maximum
+/// matching, and the number of slots they could take between them, which is one too few
+fn hall_violation(
+    adjacency: &[Vec<usize>],
+    slot_meeting: &[Option<usize>],
+    unmatched: usize,
+) -> (Vec<usize>, usize) {
+    let mut meetings = vec![unmatched];
+    let mut slots = BTreeSet::new();
+    let mut next = 0;
+    while let Some(&m) = meetings.get(next) {
+        for &slot in &adjacency[m] {
+            if slots.insert(slot) {
+                meetings.extend(slot_meeting[slot]);
+            }
+        }
+        next += 1;
+    }
+    (meetings, slots.len())
+}
+
+/// Lets the library pick the solver best suited to each problem. Either way, a schedule
+/// without a solution is reported as `ValidationError::NoSolution`; solve with `Matching`
+/// itself, or use `Schedule::diagnose`, to find out which meetings are to blame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Auto {
+    /// Used for problems which are not a `Matching`
+    pub backtracking: Backtracking,
+}
+
+impl Auto {
+    pub fn new(backtracking: Backtracking) -> Auto {
+        Auto { backtracking }
+    }
+}
+
+impl<
+        #[cfg(all(not(feature = "rayon"), feature = "serde"))] N: Display
+            + Debug
+            + Integer
+            + One
+            + Clone
+            + Copy
+            + AsPrimitive<usize>
+            + std::iter::Sum
+            + std::ops::AddAssign
+            + CheckedSub
+            + CheckedAdd
+            + serde::Serialize,
+        #[cfg(all(not(feature = "rayon"), not(feature = "serde")))] N: Display
+            + Debug
+            + Integer
+            + One
+            + Clone
+            + AsPrimitive<usize>
+            + Copy
+            + std::iter::Sum
+            + std::ops::AddAssign
+            + CheckedSub
+            + CheckedAdd,
+        #[cfg(all(feature = "rayon", feature = "serde"))] N: Display
+            + Debug
+            + Integer
+            + One
+            + Clone
+            + Copy
+            + AsPrimitive<usize>
+            + std::iter::Sum
+            + std::ops::AddAssign
+            + CheckedSub
+            + CheckedAdd
+            + std::marker::Send
+            + std::marker::Sync
+            + serde::Serialize,
+        #[cfg(all(feature = "rayon", not(feature = "serde")))] N: Display
+            + Debug
+            + Integer
+            + One
+            + AsPrimitive<usize>
+            + Clone
+            + Copy
+            + std::iter::Sum
+            + std::ops::AddAssign
+            + CheckedSub
+            + CheckedAdd
+            + std::marker::Sync
+            + std::marker::Send,
+    > Solver<N> for Auto
+{
+    fn solve(
+        &self,
+        schedule: &Schedule<N>,
+        meetings: MeetingSchedule<N>,
+    ) -> Result<ScheduleResult<N>, ValidationError<N>> {
+        if Matching::suits(schedule, &meetings) {
+            match Matching.solve(schedule, meetings) {
+                Err(ValidationError::HallViolation { .. }) => Err(ValidationError::NoSolution),
+                result => result,
+            }
+        } else {
+            self.backtracking.solve(schedule, meetings)
+        }
+    }
+}
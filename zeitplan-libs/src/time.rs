@@ -2,8 +2,17 @@ use core::cmp::Ordering;
 use itertools::Itertools;
 use log::{debug, trace};
 use num::{CheckedAdd, CheckedSub, Integer, One, Zero};
+use std::collections::BTreeSet;
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
+use std::ops::Bound::{Excluded, Included, Unbounded};
+
+mod recurrence;
+mod set;
+#[cfg(feature = "ics")]
+pub(crate) use recurrence::parse_ical_datetime;
+pub use recurrence::{Frequency, Occurrences, Recurrence, RecurrenceError, RecurrenceRule};
+pub use set::TimeSet;
 
 /// Inclusive [start, end] time range
 /// <N>: Any integer type
@@ -29,8 +38,8 @@ where
     ///
     /// let test = TimeRange::new(0, 100);
     ///
-    /// assert_eq!(test.0, 0);
-    /// assert_eq!(test.1, 100);
+    /// assert_eq!(test.start, 0);
+    /// assert_eq!(test.end, 100);
     /// ```
     pub fn new(start: N, end: N) -> TimeRange<N> {
         if end < start {
@@ -43,6 +52,31 @@ where
             TimeRange { start, end }
         }
     }
+
+    /// How many windows of `duration` starting on the `alignment` fit in this range,
+    /// not counting the first one. Used to rank meetings by how much choice they have.
+    pub(crate) fn window_span(&self, duration: N, alignment: &Alignment<N>) -> N
+    where
+        N: CheckedAdd + CheckedSub,
+    {
+        let last = match self.end.checked_sub(&(duration - <N>::one())) {
+            Some(last) if last >= self.start => last,
+            _ => return <N>::zero(),
+        };
+        let mut windows = <N>::zero();
+        let mut start = alignment.align(self.start);
+        // Count the windows from each day start or boundary up to the next one
+        while let Some(first) = start.filter(|&first| first <= last) {
+            let next = alignment.next_origin(first);
+            let until = next.map_or(last, |next| last.min(next - <N>::one()));
+            windows = windows + (until - first) / alignment.step + <N>::one();
+            start = next;
+        }
+        match windows.checked_sub(&<N>::one()) {
+            Some(windows) => windows,
+            None => <N>::zero(),
+        }
+    }
 }
 
 impl<N> Display for TimeRange<N>
@@ -179,41 +213,19 @@ where
 #[derive(Debug)]
 pub struct Blocker<N>
 where
-    N: Integer + Copy + Display + Debug,
+    N: Integer + One + Copy + Display + Debug,
 {
-    times: std::vec::IntoIter<Time<N>>,
-    count: isize,
+    times: std::vec::IntoIter<TimeRange<N>>,
 }
 
 impl<N> Iterator for Blocker<N>
 where
-    N: Integer + Copy + Display + Debug + CheckedSub,
+    N: Integer + One + Copy + Display + Debug,
 {
     type Item = TimeRange<N>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while self.count < 1 {
-            match self.times.next()? {
-                Time::Start(s) if self.count == 0 => {
-                    match self.times.next()? {
-                        Time::End(e) => {
-                            return Some(TimeRange::new(s, e));
-                        }
-                        _ => {
-                            unreachable!();
-                        }
-                    };
-                }
-                Time::End(_) => {
-                    self.count -= 1;
-                }
-                Time::Start(_) => {
-                    self.count += 1;
-                }
-            }
-        }
-
-        None
+        self.times.next()
     }
 }
 
@@ -232,31 +244,11 @@ where
     N: 'a + Integer + Copy + Display + Debug + CheckedSub + CheckedAdd,
 {
     fn blocks(&mut self, blocking: I) -> Blocker<N> {
-        let mut count = 0;
-        let chain = self
-            .time_merge()
-            .flat_map(|time| [Time::Start(time.start), Time::End(time.end)])
-            .merge(blocking.time_merge().flat_map(|time| {
-                let mut times: Vec<Time<N>> = Vec::with_capacity(2);
-
-                match time.start.checked_sub(&<N>::one()) {
-                    Some(n) => times.push(Time::End(n)),
-                    None => {
-                        count -= 1;
-                    }
-                }
-
-                if let Some(n) = time.end.checked_add(&<N>::one()) {
-                    times.push(Time::Start(n))
-                }
-
-                times
-            }))
-            .collect_vec();
+        let available: TimeSet<N> = self.collect();
+        let blocked: TimeSet<N> = blocking.collect();
 
         Blocker {
-            times: chain.into_iter(),
-            count,
+            times: available.difference(&blocked).into_iter(),
         }
     }
 }
@@ -265,7 +257,7 @@ pub trait Available<N>
 where
     N: Integer + Copy + Display + Debug,
 {
-    fn get_availability(&self, available_times: &[TimeRange<N>]) -> Vec<TimeRange<N>>;
+    fn get_availability(&self, available_times: &TimeSet<N>) -> TimeSet<N>;
 }
 
 #[derive(Debug, Clone)]
@@ -460,6 +452,63 @@ where
     }
 }
 
+/// Where windows may start: on multiples of `step`, counted from the start of each day of
+/// `slots_per_day` slots and from each of the `boundaries`, whichever came last.
+/// Without either, they are counted from slot 0.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Alignment<'a, N> {
+    pub step: N,
+    pub slots_per_day: Option<N>,
+    pub boundaries: Option<&'a BTreeSet<N>>,
+}
+
+impl<'a, N> Alignment<'a, N>
+where
+    N: Integer + One + Copy + Display + Debug + CheckedAdd,
+{
+    /// The last day start or boundary at or before `time`
+    fn origin(&self, time: N) -> N {
+        let day = self
+            .slots_per_day
+            .map_or(<N>::zero(), |days| time - time.mod_floor(&days));
+        match self.boundaries.and_then(|b| b.range(..=time).next_back()) {
+            Some(&boundary) => boundary.max(day),
+            None => day,
+        }
+    }
+
+    /// The first day start or boundary after `time`
+    fn next_origin(&self, time: N) -> Option<N> {
+        let day = self
+            .slots_per_day
+            .and_then(|days| (time - time.mod_floor(&days)).checked_add(&days));
+        let boundary = self
+            .boundaries
+            .and_then(|b| b.range((Excluded(time), Unbounded)).next().copied());
+        match (day, boundary) {
+            (Some(day), Some(boundary)) => Some(day.min(boundary)),
+            (day, boundary) => day.or(boundary),
+        }
+    }
+
+    /// The first start at or after `start` on the alignment. A step of zero has none.
+    pub fn align(&self, start: N) -> Option<N> {
+        if self.step.is_zero() {
+            return None;
+        }
+        let aligned = match (start - self.origin(start)).mod_floor(&self.step) {
+            r if r.is_zero() => Some(start),
+            r => start.checked_add(&(self.step - r)),
+        };
+        // The next day or boundary starts over, and is on the alignment itself
+        match (aligned, self.next_origin(start)) {
+            (Some(aligned), Some(next)) if aligned < next => Some(aligned),
+            (_, Some(next)) => Some(next),
+            (aligned, None) => aligned,
+        }
+    }
+}
+
 pub trait Windowed<'a, T, N>
 where
     T: Iterator<Item = &'a TimeRange<N>>,
@@ -478,6 +527,109 @@ where
     duration: N,
     time: Option<TimeRange<N>>,
     start: N,
+    step: N,
+    slots_per_day: Option<N>,
+    boundaries: Option<&'a BTreeSet<N>>,
+}
+
+impl<'a, T, N> TimeWindow<'a, T, N>
+where
+    T: Iterator<Item = &'a TimeRange<N>>,
+    N: 'a + Integer + One + Copy + Display + Debug + CheckedAdd,
+{
+    /// Only starts windows on multiples of `step`. These are counted from slot 0, or from
+    /// the start of the day set by `in_days` and the last of the boundaries set by `split_at`,
+    /// whichever came last. A `step` of zero is rejected: there are no windows on it.
+    ///
+    /// # Example
+    /// ```
+    /// use std::collections::BTreeSet;
+    /// use zeitplan_libs::time::{Windowed, TimeRange};
+    ///
+    /// let times = vec![ TimeRange::new(1, 6) ];
+    ///
+    /// assert_eq!(times.iter().windowed(2).aligned(2).collect::<Vec<_>>(),
+    ///     vec![
+    ///         TimeRange::new(2,3),
+    ///         TimeRange::new(4,5),
+    ///     ]
+    /// );
+    ///
+    /// // After a boundary, windows are aligned to the boundary
+    /// let lunch = BTreeSet::from([3]);
+    /// assert_eq!(times.iter().windowed(2).aligned(2).split_at(&lunch).collect::<Vec<_>>(),
+    ///     vec![
+    ///         TimeRange::new(3,4),
+    ///         TimeRange::new(5,6),
+    ///     ]
+    /// );
+    ///
+    /// assert_eq!(times.iter().windowed(2).aligned(0).count(), 0);
+    /// ```
+    pub fn aligned(mut self, step: N) -> TimeWindow<'a, T, N> {
+        self.step = step;
+        self
+    }
+
+    /// Counts the alignment of `aligned` from the start of each day of `slots_per_day`
+    /// slots, rather than from slot 0. Days of zero slots, or `None`, leave it at slot 0.
+    ///
+    /// # Example
+    /// ```
+    /// use zeitplan_libs::time::{Windowed, TimeRange};
+    ///
+    /// // Days of 5 slots, with windows starting every 2 slots from the start of each day
+    /// let times = vec![ TimeRange::new(0, 9) ];
+    ///
+    /// assert_eq!(times.iter().windowed(2).aligned(2).in_days(Some(5)).collect::<Vec<_>>(),
+    ///     vec![
+    ///         TimeRange::new(0,1),
+    ///         TimeRange::new(2,3),
+    ///         TimeRange::new(4,5),
+    ///         TimeRange::new(5,6),
+    ///         TimeRange::new(7,8),
+    ///     ]
+    /// );
+    /// ```
+    pub fn in_days(mut self, slots_per_day: Option<N>) -> TimeWindow<'a, T, N> {
+        self.slots_per_day = slots_per_day.filter(|days| !days.is_zero());
+        self
+    }
+
+    /// Never lets a window cross one of the `boundaries`: a window may not hold both
+    /// the slot before a boundary and the boundary itself
+    ///
+    /// # Example
+    /// ```
+    /// use std::collections::BTreeSet;
+    /// use zeitplan_libs::time::{Windowed, TimeRange};
+    ///
+    /// let times = vec![ TimeRange::new(0, 5) ];
+    /// let lunch = BTreeSet::from([3]);
+    ///
+    /// assert_eq!(times.iter().windowed(2).split_at(&lunch).collect::<Vec<_>>(),
+    ///     vec![
+    ///         TimeRange::new(0,1),
+    ///         TimeRange::new(1,2),
+    ///         TimeRange::new(3,4),
+    ///         TimeRange::new(4,5),
+    ///     ]
+    /// );
+    /// ```
+    pub fn split_at(mut self, boundaries: &'a BTreeSet<N>) -> TimeWindow<'a, T, N> {
+        self.boundaries = Some(boundaries);
+        self
+    }
+
+    /// The first start at or after `start` on the alignment
+    fn align(&self, start: N) -> Option<N> {
+        Alignment {
+            step: self.step,
+            slots_per_day: self.slots_per_day,
+            boundaries: self.boundaries,
+        }
+        .align(start)
+    }
 }
 
 impl<'a, T, N> Iterator for TimeWindow<'a, T, N>
@@ -490,38 +642,50 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         let zero_duration = self.duration.checked_sub(&<N>::one())?;
         loop {
-            match self.time {
-                Some(time) => {
-                    if self.start.checked_add(&zero_duration)? > time.end {
-                        self.time = None;
-                        continue;
+            let time = match self.time {
+                Some(time) => time,
+                None => {
+                    let time = *self.collection.next()?;
+                    match self.align(time.start) {
+                        Some(start) => {
+                            self.time = Some(time);
+                            self.start = start;
+                        }
+                        None => continue,
                     }
-
-                    let curr_start = self.start;
-                    self.start = self.start.checked_add(&<N>::one())?;
-
-                    return Some(TimeRange::new(curr_start, curr_start + zero_duration));
+                    time
                 }
-                None => match self.collection.next() {
-                    Some(time) => {
-                        self.time = Some(*time);
+            };
 
-                        let curr_start = time.start;
-
-                        if curr_start.checked_add(&zero_duration)? > time.end {
-                            self.time = None;
-                            continue;
-                        }
+            let curr_start = self.start;
+            let curr_end = curr_start.checked_add(&zero_duration)?;
+            if curr_end > time.end {
+                self.time = None;
+                continue;
+            }
 
-                        self.start = curr_start.checked_add(&<N>::one())?;
+            // Windows may not cross a boundary, so carry on from the boundary
+            if let Some(&boundary) = self
+                .boundaries
+                .and_then(|b| b.range((Excluded(curr_start), Included(curr_end))).next())
+            {
+                match self.align(boundary) {
+                    Some(start) => self.start = start,
+                    None => self.time = None,
+                }
+                continue;
+            }
 
-                        return Some(TimeRange::new(curr_start, curr_start + zero_duration));
-                    }
-                    None => {
-                        return None;
-                    }
-                },
+            // Past the last slot there is, nothing else of this range can follow
+            match curr_start
+                .checked_add(&<N>::one())
+                .and_then(|start| self.align(start))
+            {
+                Some(start) => self.start = start,
+                None => self.time = None,
             }
+
+            return Some(TimeRange::new(curr_start, curr_end));
         }
     }
 }
@@ -563,6 +727,9 @@ where
             duration,
             time: None,
             start: <N>::zero(),
+            step: <N>::one(),
+            slots_per_day: None,
+            boundaries: None,
         }
         /*
         let mut windows: Vec<TimeRange<N>> = Vec::with_capacity(self.size_hint().1.unwrap_or(0));
@@ -0,0 +1,416 @@
+use crate::calendar::SlotCalendar;
+use crate::time::{TimeRange, TimeSet};
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveDateTime, Weekday};
+use num::{FromPrimitive, Integer, One, ToPrimitive};
+use std::convert::TryFrom;
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum RecurrenceError {
+    #[error("Recurrence rule is missing FREQ")]
+    MissingFrequency,
+    #[error("Unsupported recurrence rule part {0}")]
+    UnsupportedPart(String),
+    #[error("Invalid value for {part}: {value}")]
+    InvalidValue { part: String, value: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// The supported subset of an RFC 5545 RRULE:
+/// `FREQ` (DAILY, WEEKLY or MONTHLY), `INTERVAL`, `BYDAY`, `COUNT` and `UNTIL`.
+///
+/// `BYDAY` only accepts plain weekdays (`MO`, not `1MO`), and can not be combined
+/// with a MONTHLY frequency.
+///
+/// # Examples
+/// ```
+/// use chrono::Weekday;
+/// use zeitplan_libs::time::{Frequency, RecurrenceRule};
+///
+/// let rule: RecurrenceRule = "FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,TH;COUNT=4".parse().unwrap();
+///
+/// assert_eq!(rule.frequency, Frequency::Weekly);
+/// assert_eq!(rule.interval, 2);
+/// assert_eq!(rule.by_day, vec![Weekday::Tue, Weekday::Thu]);
+/// assert_eq!(rule.count, Some(4));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<Weekday>,
+    pub count: Option<u32>,
+    /// Inclusive; occurrences starting after this are not generated
+    pub until: Option<NaiveDateTime>,
+}
+
+impl RecurrenceRule {
+    pub fn new(frequency: Frequency) -> RecurrenceRule {
+        RecurrenceRule {
+            frequency,
+            interval: 1,
+            by_day: vec![],
+            count: None,
+            until: None,
+        }
+    }
+}
+
+fn invalid(part: &str, value: &str) -> RecurrenceError {
+    RecurrenceError::InvalidValue {
+        part: part.to_string(),
+        value: value.to_string(),
+    }
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    match value {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Parses an iCalendar DATE or DATE-TIME, such as `20220802` or `20220802T140000Z`.
+/// UTC and floating times are both returned as they are written.
+pub(crate) fn parse_ical_datetime(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim_end_matches('Z');
+    NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y%m%d")
+                .ok()?
+                .and_hms_opt(0, 0, 0)
+        })
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = RecurrenceError;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        let rule = rule.trim();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+
+        let mut frequency = None;
+        let mut parsed = RecurrenceRule::new(Frequency::Daily);
+
+        for part in rule.split(';').filter(|part| !part.is_empty()) {
+            let (name, value) = part.split_once('=').ok_or_else(|| invalid(part, ""))?;
+
+            match name {
+                "FREQ" => {
+                    frequency = Some(match value {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        _ => return Err(invalid(name, value)),
+                    })
+                }
+                "INTERVAL" => {
+                    parsed.interval = value
+                        .parse()
+                        .ok()
+                        .filter(|interval| *interval > 0)
+                        .ok_or_else(|| invalid(name, value))?
+                }
+                "BYDAY" => {
+                    parsed.by_day = value
+                        .split(',')
+                        .map(|day| parse_weekday(day).ok_or_else(|| invalid(name, day)))
+                        .collect::<Result<_, _>>()?
+                }
+                "COUNT" => parsed.count = Some(value.parse().map_err(|_| invalid(name, value))?),
+                "UNTIL" => {
+                    parsed.until =
+                        Some(parse_ical_datetime(value).ok_or_else(|| invalid(name, value))?)
+                }
+                // Weeks always start on Monday, which is also the RFC 5545 default
+                "WKST" if value == "MO" => {}
+                _ => return Err(RecurrenceError::UnsupportedPart(part.to_string())),
+            }
+        }
+
+        parsed.frequency = frequency.ok_or(RecurrenceError::MissingFrequency)?;
+        if parsed.frequency == Frequency::Monthly && !parsed.by_day.is_empty() {
+            return Err(RecurrenceError::UnsupportedPart(
+                "BYDAY with FREQ=MONTHLY".to_string(),
+            ));
+        }
+
+        parsed.by_day.sort_by_key(|day| day.num_days_from_monday());
+        parsed.by_day.dedup();
+        Ok(parsed)
+    }
+}
+
+/// A recurring event: the first occurrence starts at `start`, every occurrence
+/// lasts `duration`, and further occurrences follow `rule`.
+/// Occurrences starting at one of the `exdates` are left out.
+///
+/// Times are wall-clock times in the same space as the `SlotCalendar` used to expand them.
+///
+/// # Examples
+/// ```
+/// use chrono::{Duration, NaiveDate};
+/// use zeitplan_libs::calendar::SlotCalendar;
+/// use zeitplan_libs::participant::Participant;
+/// use zeitplan_libs::time::{Recurrence, TimeRange};
+///
+/// // Hourly slots over the week starting Monday, 2022-08-01
+/// let monday = NaiveDate::from_ymd_opt(2022, 8, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+/// let calendar: SlotCalendar<u8> =
+///     SlotCalendar::new(monday, Duration::hours(1), monday + Duration::weeks(1)).unwrap();
+///
+/// // Lunch on weekdays, except Wednesday
+/// let lunch = Recurrence::new(
+///     monday + Duration::hours(12),
+///     Duration::hours(1),
+///     "FREQ=DAILY;BYDAY=MO,TU,WE,TH,FR".parse().unwrap(),
+/// )
+/// .except(monday + Duration::days(2) + Duration::hours(12));
+///
+/// let participant = Participant::new("1", lunch.blocked_times(&calendar));
+///
+/// assert_eq!(
+///     participant.blocked_times.as_slice(),
+///     &[
+///         TimeRange::new(12, 12),
+///         TimeRange::new(36, 36),
+///         TimeRange::new(84, 84),
+///         TimeRange::new(108, 108),
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recurrence {
+    pub start: NaiveDateTime,
+    pub duration: Duration,
+    pub rule: RecurrenceRule,
+    pub exdates: Vec<NaiveDateTime>,
+}
+
+impl Recurrence {
+    pub fn new(start: NaiveDateTime, duration: Duration, rule: RecurrenceRule) -> Recurrence {
+        Recurrence {
+            start,
+            duration,
+            rule,
+            exdates: vec![],
+        }
+    }
+
+    /// Leaves out the occurrence starting at `exdate`
+    pub fn except(mut self, exdate: NaiveDateTime) -> Recurrence {
+        self.exdates.push(exdate);
+        self
+    }
+
+    /// Every occurrence as a [start, end) pair, in order.
+    /// Without a `COUNT` or `UNTIL` this never ends.
+    pub fn occurrences(&self) -> Occurrences<'_> {
+        Occurrences {
+            recurrence: self,
+            period: 0,
+            pending: vec![],
+            generated: 0,
+        }
+    }
+
+    /// Occurrences which start before the end of the horizon of `calendar`
+    fn within<'a, N>(
+        &'a self,
+        calendar: &'a SlotCalendar<N>,
+    ) -> impl Iterator<Item = (NaiveDateTime, NaiveDateTime)> + 'a
+    where
+        N: Integer + One + Copy + Display + Debug + FromPrimitive + ToPrimitive,
+    {
+        let horizon_end = calendar.end_of::<NaiveDateTime>(calendar.horizon().end);
+        self.occurrences()
+            .take_while(move |(start, _)| horizon_end.map(|end| *start < end).unwrap_or(true))
+    }
+
+    /// Lazily expands the occurrences within the horizon of `calendar` into slots.
+    /// Slots partially covered by an occurrence are included.
+    pub fn blocked<'a, N>(
+        &'a self,
+        calendar: &'a SlotCalendar<N>,
+    ) -> impl Iterator<Item = TimeRange<N>> + 'a
+    where
+        N: Integer + One + Copy + Display + Debug + FromPrimitive + ToPrimitive,
+    {
+        self.within(calendar)
+            .filter_map(move |(start, end)| calendar.blocked(start, end))
+    }
+
+    /// Lazily expands the occurrences within the horizon of `calendar` into slots.
+    /// Only slots entirely covered by an occurrence are included.
+    pub fn available<'a, N>(
+        &'a self,
+        calendar: &'a SlotCalendar<N>,
+    ) -> impl Iterator<Item = TimeRange<N>> + 'a
+    where
+        N: Integer + One + Copy + Display + Debug + FromPrimitive + ToPrimitive,
+    {
+        self.within(calendar)
+            .filter_map(move |(start, end)| calendar.available(start, end))
+    }
+
+    /// Every occurrence within the horizon of `calendar`, as `Participant::blocked_times`
+    pub fn blocked_times<N>(&self, calendar: &SlotCalendar<N>) -> TimeSet<N>
+    where
+        N: Integer + One + Copy + Display + Debug + FromPrimitive + ToPrimitive,
+    {
+        self.blocked(calendar).collect()
+    }
+
+    /// Every occurrence within the horizon of `calendar`, as `Schedule::availability`
+    pub fn availability<N>(&self, calendar: &SlotCalendar<N>) -> TimeSet<N>
+    where
+        N: Integer + One + Copy + Display + Debug + FromPrimitive + ToPrimitive,
+    {
+        self.available(calendar).collect()
+    }
+}
+
+/// Iterator over the [start, end) pairs of a `Recurrence`
+#[derive(Debug, Clone)]
+pub struct Occurrences<'a> {
+    recurrence: &'a Recurrence,
+    /// Index of the next day, week or month to generate candidates for
+    period: i64,
+    /// Candidates of the current period, in reverse order
+    pending: Vec<NaiveDateTime>,
+    generated: u32,
+}
+
+impl<'a> Occurrences<'a> {
+    /// Whether some day stepped to by a DAILY rule falls on one of its `BYDAY` weekdays.
+    /// Stepping by a multiple of a week only ever lands on the weekday of the start.
+    #[allow(clippy::manual_is_multiple_of)]
+    fn reaches_by_day(&self) -> bool {
+        let rule = &self.recurrence.rule;
+        rule.frequency != Frequency::Daily
+            || rule.by_day.is_empty()
+            || rule.interval % 7 != 0
+            || rule.by_day.contains(&self.recurrence.start.weekday())
+    }
+
+    /// The first day of the `period`th day, week or month of the rule, along with
+    /// the candidate start times within it.
+    /// Returns `None` once the period can not be represented.
+    fn candidates(&self, period: i64) -> Option<(NaiveDateTime, Vec<NaiveDateTime>)> {
+        let start = self.recurrence.start;
+        let rule = &self.recurrence.rule;
+        let step = period.checked_mul(rule.interval as i64)?;
+
+        let (first, days) = match rule.frequency {
+            Frequency::Daily => {
+                let day = start.date().checked_add_signed(Duration::days(step))?;
+                if rule.by_day.is_empty() || rule.by_day.contains(&day.weekday()) {
+                    (day, vec![day])
+                } else {
+                    (day, vec![])
+                }
+            }
+            Frequency::Weekly => {
+                let monday = start
+                    .date()
+                    .checked_sub_signed(Duration::days(
+                        start.weekday().num_days_from_monday() as i64
+                    ))?
+                    .checked_add_signed(Duration::weeks(step))?;
+
+                let days = if rule.by_day.is_empty() {
+                    vec![monday.checked_add_signed(Duration::days(
+                        start.weekday().num_days_from_monday() as i64,
+                    ))?]
+                } else {
+                    rule.by_day
+                        .iter()
+                        .filter_map(|day| {
+                            monday.checked_add_signed(Duration::days(
+                                day.num_days_from_monday() as i64
+                            ))
+                        })
+                        .collect()
+                };
+                (monday, days)
+            }
+            Frequency::Monthly => {
+                let month = start
+                    .date()
+                    .with_day(1)?
+                    .checked_add_months(Months::new(u32::try_from(step).ok()?))?;
+                // Months without this day are skipped, as RFC 5545 does
+                (month, month.with_day(start.day()).into_iter().collect())
+            }
+        };
+
+        Some((
+            first.and_hms_opt(0, 0, 0)?,
+            days.into_iter()
+                .map(|day| day.and_time(start.time()))
+                .filter(|time| *time >= start)
+                .collect(),
+        ))
+    }
+}
+
+impl<'a> Iterator for Occurrences<'a> {
+    type Item = (NaiveDateTime, NaiveDateTime);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rule = &self.recurrence.rule;
+        if !self.reaches_by_day() {
+            return None;
+        }
+
+        loop {
+            if rule
+                .count
+                .map(|count| self.generated >= count)
+                .unwrap_or(false)
+            {
+                return None;
+            }
+
+            let start = match self.pending.pop() {
+                Some(start) => start,
+                None => {
+                    let (first, mut candidates) = self.candidates(self.period)?;
+                    // Periods without any candidates must not run on past UNTIL
+                    if rule.until.map(|until| first > until).unwrap_or(false) {
+                        return None;
+                    }
+                    candidates.reverse();
+                    self.pending = candidates;
+                    self.period += 1;
+                    continue;
+                }
+            };
+
+            if rule.until.map(|until| start > until).unwrap_or(false) {
+                return None;
+            }
+
+            // Excluded occurrences still count towards COUNT
+            self.generated += 1;
+            if !self.recurrence.exdates.contains(&start) {
+                return Some((start, start + self.recurrence.duration));
+            }
+        }
+    }
+}
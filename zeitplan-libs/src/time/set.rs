@@ -0,0 +1,403 @@
+use crate::time::TimeRange;
+#[cfg(feature = "serde")]
+use crate::time::Validate;
+use num::{CheckedAdd, Integer, One};
+use std::fmt::{Debug, Display};
+use std::iter::FromIterator;
+
+/// A set of times, stored as sorted, non-overlapping and non-adjacent
+/// `TimeRange`s.
+///
+/// Every operation keeps the set normalized, so there is no need to
+/// `time_merge()` the result again before using it.
+///
+/// # Examples
+/// ```
+/// use zeitplan_libs::time::{TimeRange, TimeSet};
+///
+/// let times: TimeSet<u8> = vec![
+///     TimeRange::new(4, 6),
+///     TimeRange::new(0, 1),
+///     TimeRange::new(2, 2),
+/// ].into();
+///
+/// assert_eq!(times.as_slice(), &[TimeRange::new(0, 2), TimeRange::new(4, 6)]);
+/// assert_eq!(times.len_in_slots(), Some(6));
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
+pub struct TimeSet<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    ranges: Vec<TimeRange<N>>,
+}
+
+impl<N> Default for TimeSet<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    fn default() -> Self {
+        TimeSet { ranges: Vec::new() }
+    }
+}
+
+impl<N> TimeSet<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    /// Constructs an empty set
+    pub fn new() -> TimeSet<N> {
+        TimeSet::default()
+    }
+
+    /// The normalized ranges of this set, in ascending order
+    pub fn as_slice(&self) -> &[TimeRange<N>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, TimeRange<N>> {
+        self.ranges.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The first and last time contained in this set
+    pub fn bounds(&self) -> Option<TimeRange<N>> {
+        Some(TimeRange::new(
+            self.ranges.first()?.start,
+            self.ranges.last()?.end,
+        ))
+    }
+
+    /// Keeps only the ranges for which `f` returns true.
+    /// Dropping whole ranges never breaks the normalization of the set.
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&TimeRange<N>) -> bool,
+    {
+        self.ranges.retain(f)
+    }
+
+    /// Index of the first range which ends at or after `time`
+    fn first_ending_after(&self, time: N) -> usize {
+        self.ranges.partition_point(|range| range.end < time)
+    }
+
+    /// True if every time within `time` is part of this set
+    ///
+    /// # Examples
+    /// ```
+    /// use zeitplan_libs::time::{TimeRange, TimeSet};
+    ///
+    /// let times: TimeSet<u8> = vec![TimeRange::new(0, 2), TimeRange::new(4, 6)].into();
+    ///
+    /// assert!(times.contains(&TimeRange::new(4, 5)));
+    /// assert!(!times.contains(&TimeRange::new(2, 4)));
+    /// ```
+    pub fn contains(&self, time: &TimeRange<N>) -> bool {
+        self.ranges
+            .get(self.first_ending_after(time.start))
+            .map(|range| range.start <= time.start && time.end <= range.end)
+            .unwrap_or(false)
+    }
+
+    /// True if any time within `time` is part of this set
+    ///
+    /// # Examples
+    /// ```
+    /// use zeitplan_libs::time::{TimeRange, TimeSet};
+    ///
+    /// let times: TimeSet<u8> = vec![TimeRange::new(0, 2), TimeRange::new(4, 6)].into();
+    ///
+    /// assert!(times.overlaps(&TimeRange::new(2, 4)));
+    /// assert!(!times.overlaps(&TimeRange::new(3, 3)));
+    /// ```
+    pub fn overlaps(&self, time: &TimeRange<N>) -> bool {
+        self.ranges
+            .get(self.first_ending_after(time.start))
+            .map(|range| range.start <= time.end)
+            .unwrap_or(false)
+    }
+
+    /// The times contained in both sets
+    ///
+    /// # Examples
+    /// ```
+    /// use zeitplan_libs::time::{TimeRange, TimeSet};
+    ///
+    /// let a: TimeSet<u8> = vec![TimeRange::new(0, 5), TimeRange::new(8, 10)].into();
+    /// let b: TimeSet<u8> = vec![TimeRange::new(3, 9)].into();
+    ///
+    /// assert_eq!(
+    ///     a.intersection(&b).as_slice(),
+    ///     &[TimeRange::new(3, 5), TimeRange::new(8, 9)]
+    /// );
+    /// ```
+    pub fn intersection(&self, other: &TimeSet<N>) -> TimeSet<N> {
+        let mut ranges = Vec::new();
+        let mut left = self.ranges.iter().peekable();
+        let mut right = other.ranges.iter().peekable();
+
+        while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+            let start = l.start.max(r.start);
+            let end = l.end.min(r.end);
+            if start <= end {
+                ranges.push(TimeRange::new(start, end));
+            }
+
+            if l.end < r.end {
+                left.next();
+            } else {
+                right.next();
+            }
+        }
+
+        TimeSet { ranges }
+    }
+
+    /// Adds `time` to this set
+    pub fn insert(&mut self, time: TimeRange<N>) {
+        *self = self.union(&time.into());
+    }
+
+    /// The times contained in either set
+    ///
+    /// # Examples
+    /// ```
+    /// use zeitplan_libs::time::{TimeRange, TimeSet};
+    ///
+    /// let a: TimeSet<u8> = vec![TimeRange::new(0, 2), TimeRange::new(8, 10)].into();
+    /// let b: TimeSet<u8> = vec![TimeRange::new(3, 4)].into();
+    ///
+    /// assert_eq!(
+    ///     a.union(&b).as_slice(),
+    ///     &[TimeRange::new(0, 4), TimeRange::new(8, 10)]
+    /// );
+    /// ```
+    pub fn union(&self, other: &TimeSet<N>) -> TimeSet<N> {
+        if other.is_empty() {
+            return self.clone();
+        } else if self.is_empty() {
+            return other.clone();
+        }
+
+        self.ranges.iter().chain(other.ranges.iter()).collect()
+    }
+
+    /// The times contained in `self`, but not in `other`
+    ///
+    /// # Examples
+    /// ```
+    /// use zeitplan_libs::time::{TimeRange, TimeSet};
+    ///
+    /// let a: TimeSet<u8> = vec![TimeRange::new(0, 10)].into();
+    /// let b: TimeSet<u8> = vec![TimeRange::new(0, 0), TimeRange::new(3, 4)].into();
+    ///
+    /// assert_eq!(
+    ///     a.difference(&b).as_slice(),
+    ///     &[TimeRange::new(1, 2), TimeRange::new(5, 10)]
+    /// );
+    /// ```
+    pub fn difference(&self, other: &TimeSet<N>) -> TimeSet<N> {
+        let mut ranges = Vec::with_capacity(self.ranges.len());
+        let mut blocked = other.ranges.iter().peekable();
+
+        'available: for range in self.ranges.iter() {
+            let mut start = range.start;
+
+            // Blocks ending before this range can never affect the following ranges
+            while blocked.next_if(|b| b.end < start).is_some() {}
+
+            while let Some(block) = blocked.peek() {
+                if block.start > range.end {
+                    break;
+                }
+
+                if block.start > start {
+                    ranges.push(TimeRange::new(start, block.start - <N>::one()));
+                }
+
+                if block.end >= range.end {
+                    // The block may still cover the start of the next range
+                    continue 'available;
+                }
+
+                start = block.end + <N>::one();
+                blocked.next();
+            }
+
+            ranges.push(TimeRange::new(start, range.end));
+        }
+
+        TimeSet { ranges }
+    }
+
+    /// The times within `horizon` which are *not* contained in this set
+    ///
+    /// # Examples
+    /// ```
+    /// use zeitplan_libs::time::{TimeRange, TimeSet};
+    ///
+    /// let blocked: TimeSet<u8> = vec![TimeRange::new(2, 3), TimeRange::new(8, 12)].into();
+    ///
+    /// assert_eq!(
+    ///     blocked.complement(TimeRange::new(0, 9)).as_slice(),
+    ///     &[TimeRange::new(0, 1), TimeRange::new(4, 7)]
+    /// );
+    /// ```
+    pub fn complement(&self, horizon: TimeRange<N>) -> TimeSet<N> {
+        TimeSet::from(horizon).difference(self)
+    }
+}
+
+impl<N> TimeSet<N>
+where
+    N: Integer + One + Copy + Display + Debug + CheckedAdd,
+{
+    /// Counts the number of slots within this set.
+    /// Returns `None` if the count does not fit within `N`.
+    pub fn len_in_slots(&self) -> Option<N> {
+        self.ranges.iter().try_fold(<N>::zero(), |sum, time| {
+            sum.checked_add(&(time.end - time.start).checked_add(&<N>::one())?)
+        })
+    }
+}
+
+impl<N> From<TimeRange<N>> for TimeSet<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    fn from(time: TimeRange<N>) -> Self {
+        TimeSet {
+            ranges: vec![TimeRange::new(time.start, time.end)],
+        }
+    }
+}
+
+impl<N> From<Vec<TimeRange<N>>> for TimeSet<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    fn from(times: Vec<TimeRange<N>>) -> Self {
+        times.into_iter().collect()
+    }
+}
+
+impl<N> From<&[TimeRange<N>]> for TimeSet<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    fn from(times: &[TimeRange<N>]) -> Self {
+        times.iter().collect()
+    }
+}
+
+impl<N> From<TimeSet<N>> for Vec<TimeRange<N>>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    fn from(set: TimeSet<N>) -> Self {
+        set.ranges
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, N> serde::Deserialize<'de> for TimeSet<N>
+where
+    N: Integer + One + Copy + Display + Debug + serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let times = Vec::<TimeRange<N>>::deserialize(deserializer)?;
+        times
+            .iter()
+            .try_for_each(|t| t.validate())
+            .map_err(serde::de::Error::custom)?;
+        Ok(times.into())
+    }
+}
+
+impl<'a, N> FromIterator<&'a TimeRange<N>> for TimeSet<N>
+where
+    N: 'a + Integer + One + Copy + Display + Debug,
+{
+    fn from_iter<T: IntoIterator<Item = &'a TimeRange<N>>>(iter: T) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+impl<N> FromIterator<TimeRange<N>> for TimeSet<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    fn from_iter<T: IntoIterator<Item = TimeRange<N>>>(iter: T) -> Self {
+        // Ranges constructed by hand may have been given backwards
+        let mut times = iter
+            .into_iter()
+            .map(|t| TimeRange::new(t.start, t.end))
+            .collect::<Vec<_>>();
+        times.sort_unstable_by_key(|t| t.start);
+
+        let mut ranges: Vec<TimeRange<N>> = Vec::with_capacity(times.len());
+        for time in times {
+            match ranges.last_mut() {
+                // `time.start - last.end` cannot overflow, where `last.end + 1` could
+                Some(last) if time.start <= last.end || time.start - last.end == <N>::one() => {
+                    last.end = last.end.max(time.end);
+                }
+                _ => ranges.push(time),
+            }
+        }
+
+        TimeSet { ranges }
+    }
+}
+
+impl<N> Extend<TimeRange<N>> for TimeSet<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    fn extend<T: IntoIterator<Item = TimeRange<N>>>(&mut self, iter: T) {
+        let ranges = std::mem::take(&mut self.ranges);
+        *self = ranges.into_iter().chain(iter).collect();
+    }
+}
+
+impl<N> IntoIterator for TimeSet<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    type Item = TimeRange<N>;
+    type IntoIter = std::vec::IntoIter<TimeRange<N>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.into_iter()
+    }
+}
+
+impl<'a, N> IntoIterator for &'a TimeSet<N>
+where
+    N: Integer + One + Copy + Display + Debug,
+{
+    type Item = &'a TimeRange<N>;
+    type IntoIter = std::slice::Iter<'a, TimeRange<N>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.iter()
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, N> arbitrary::Arbitrary<'a> for TimeSet<N>
+where
+    N: Integer + Copy + arbitrary::Arbitrary<'a> + Display + Debug,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(u.arbitrary::<Vec<TimeRange<N>>>()?.into())
+    }
+}